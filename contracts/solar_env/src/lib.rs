@@ -0,0 +1,102 @@
+//! The Solarchain environment for ink! contracts.
+//!
+//! Contracts switch to it with `#[ink::contract(env = solar_env::SolarEnvironment)]` and then
+//! reach the runtime's chain extension through `self.env().extension()`, e.g.
+//! `self.env().extension().free_balance(account)` instead of calling `balance_of` on a
+//! separate FT contract.
+//!
+//! Function IDs and status codes must stay in sync with `runtime/src/chain_extension.rs`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_env::{AccountId, DefaultEnvironment, Environment};
+use ink_lang as ink;
+use scale::{Decode, Encode};
+
+/// An asset ID of `pallet_assets`.
+pub type AssetId = u32;
+
+/// A collection ID.
+pub type CollectionId = u64;
+
+/// A token ID.
+pub type TokenId = u64;
+
+type Balance = <DefaultEnvironment as Environment>::Balance;
+
+/// Native balances of an account, as stored by `pallet_balances`.
+#[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AccountData {
+    pub free: Balance,
+    pub reserved: Balance,
+    pub misc_frozen: Balance,
+    pub fee_frozen: Balance,
+}
+
+#[ink::chain_extension]
+pub trait SolarExtension {
+    type ErrorCode = SolarError;
+
+    /// Returns the free native balance of `account`.
+    #[ink(extension = 1001, returns_result = false, handle_status = false)]
+    fn free_balance(account: AccountId) -> Balance;
+
+    /// Returns the free, reserved and frozen native balances of `account`.
+    #[ink(extension = 1002, returns_result = false, handle_status = false)]
+    fn account_balance(account: AccountId) -> AccountData;
+
+    /// Transfers `value` native tokens from the calling contract to `to`.
+    #[ink(extension = 1003, returns_result = false)]
+    fn transfer(to: AccountId, value: Balance);
+
+    /// Returns the balance of `account` in the asset `asset_id`, zero for unknown assets.
+    #[ink(extension = 1004, returns_result = false, handle_status = false)]
+    fn asset_balance(asset_id: AssetId, account: AccountId) -> Balance;
+
+    /// Returns the owner of the token.
+    #[ink(extension = 1101, returns_result = false, handle_status = false)]
+    fn nft_owner_of(collection_id: CollectionId, token_id: TokenId) -> Option<AccountId>;
+
+    /// Returns the number of tokens owned by `account`.
+    #[ink(extension = 1102, returns_result = false, handle_status = false)]
+    fn nft_balance_of(account: AccountId) -> u64;
+
+    /// Returns whether a registrar has judged the identity of `account` as `Reasonable` or
+    /// `KnownGood`, i.e. whether it is a verified creator.
+    #[ink(extension = 1201, returns_result = false, handle_status = false)]
+    fn is_verified_creator(account: AccountId) -> bool;
+}
+
+#[derive(Encode, Decode, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum SolarError {
+    /// The native transfer failed, e.g. because of insufficient balance.
+    TransferFailed,
+}
+
+impl ink_env::chain_extension::FromStatusCode for SolarError {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match status_code {
+            0 => Ok(()),
+            1 => Err(Self::TransferFailed),
+            _ => panic!("encountered unknown status code"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum SolarEnvironment {}
+
+impl Environment for SolarEnvironment {
+    const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <DefaultEnvironment as Environment>::AccountId;
+    type Balance = <DefaultEnvironment as Environment>::Balance;
+    type Hash = <DefaultEnvironment as Environment>::Hash;
+    type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+    type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+
+    type ChainExtension = SolarExtension;
+}
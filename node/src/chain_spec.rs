@@ -1,18 +1,63 @@
-use sc_service::ChainType;
+use sc_service::{ChainType, Properties};
+use serde::{de, Deserialize, Deserializer};
 use solar_node_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, NodeAuthorizationConfig,
-	Signature, SudoConfig, SystemConfig, WASM_BINARY,
+	opaque::SessionKeys, AccountId, AssetId, AssetsConfig, AuraConfig, Balance, BalancesConfig,
+	BlockNumber, BlockRewardConfig, CouncilMembershipConfig, ExistentialDeposit, GenesisConfig,
+	GrandpaConfig, MarketConfig, NodeAuthorizationConfig, SS58Prefix, SessionConfig, Signature,
+	SudoConfig, SystemConfig, TechnicalMembershipConfig, Treasury, ValidatorSetConfig,
+	VestingConfig, WASM_BINARY,
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::OpaquePeerId; // A struct wraps Vec<u8>, represents as our `PeerId`.
-use sp_core::{crypto::AccountId32, sr25519, Pair, Public};
+use sp_core::{crypto::Ss58Codec, sr25519, Get, Pair, Public};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
-use sp_runtime::traits::{IdentifyAccount, Verify};
-use std::str::FromStr; // The genesis config that serves for our pallet.
+use sp_runtime::{
+	traits::{IdentifyAccount, Verify},
+	Perbill,
+};
+use std::{collections::BTreeSet, fmt, path::Path};
+
+use crate::genesis::GenesisDescription;
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
+/// Asset ID of the Solar stable token created at genesis.
+const SOLAR_STABLE_ASSET_ID: AssetId = 1;
+
+/// Balance of every endowed account of the development and local testnet presets.
+const ENDOWMENT: Balance = 1 << 60;
+
+/// Peer IDs and owners of the nodes allowed by `pallet_node_authorization` on the development and
+/// local testnet presets.
+const TESTNET_WELL_KNOWN_NODES: [(&str, &str); 4] = [
+	(
+		"12D3KooWCySiYrhevMhVKXGZYEcUUViyti6wRSjXgnmN81D7g8ZY",
+		"5EWX1uk3P2wxLJSr5ZT3jSEPAPse67guqV5ASjU4yL89LN18",
+	),
+	(
+		"12D3KooWKHai5by8FAf5QmJMo53n1AfQzvtMvDwhzKtx4LRPL8Yn",
+		"5GHbL5YWjsaLL24PRAiX2YK8b3vcoqvStnMvRR75YrD5jTty",
+	),
+	(
+		"12D3KooWGHPNxMp3YwZYvDV7grbKsrCgKFEUSYjzjRRoKQ2JBT8S",
+		"5CDHFYGAaiK81vwaELU95fvDW8DYJkAkEqF5SVFwPzGjnSay",
+	),
+	(
+		"12D3KooWEU1k7baQEXDiXrxmmmq4Rj7kG7WKPZRm4Ru3hi11StN6",
+		"5G6KbEQTnQ5agwCWBqkij277wrAACJA37RJ157bUB842rExp",
+	),
+];
+
+/// Symbol of the native token.
+pub const TOKEN_SYMBOL: &str = "SOLAR";
+
+/// Decimals of the native token, the exponent of `UNIT`.
+pub const TOKEN_DECIMALS: u8 = 12;
+
+/// Genesis description of the staging preset.
+const STAGING_GENESIS: &str = include_str!("../res/staging.toml");
+
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
 
@@ -33,13 +78,251 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// Generate a validator account and its Aura and Grandpa session keys.
+pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId) {
+	(
+		get_account_id_from_seed::<sr25519::Public>(s),
+		get_from_seed::<AuraId>(s),
+		get_from_seed::<GrandpaId>(s),
+	)
+}
+
+fn session_keys(aura: AuraId, grandpa: GrandpaId) -> SessionKeys {
+	SessionKeys { aura, grandpa }
+}
+
+/// Chain properties for wallets: token symbol, decimals and address format.
+pub fn properties(token_symbol: &str, token_decimals: u8, ss58_format: u16) -> Properties {
+	let mut properties = Properties::new();
+	properties.insert("tokenSymbol".into(), token_symbol.into());
+	properties.insert("tokenDecimals".into(), token_decimals.into());
+	properties.insert("ss58Format".into(), ss58_format.into());
+	properties
+}
+
+/// Properties of the native token and the runtime's `SS58Prefix`.
+fn solar_properties() -> Properties {
+	properties(TOKEN_SYMBOL, TOKEN_DECIMALS, SS58Prefix::get())
+}
+
+/// Parses the SS58 address or public key `value` of the field `what`.
+pub fn parse_ss58<T: Ss58Codec>(what: &str, value: &str) -> Result<T, String> {
+	T::from_ss58check(value).map_err(|e| format!("{}: invalid SS58 `{}`: {:?}", what, value, e))
+}
+
+/// Parses a node allowed by `pallet_node_authorization` from its base58 peer ID and the SS58
+/// address of its owner.
+pub fn well_known_node(peer_id: &str, owner: &str) -> Result<(OpaquePeerId, AccountId), String> {
+	let bytes = bs58::decode(peer_id)
+		.into_vec()
+		.map_err(|e| format!("well-known node: invalid base58 peer ID `{}`: {}", peer_id, e))?;
+	let owner = parse_ss58(&format!("owner of well-known node {}", peer_id), owner)?;
+	Ok((OpaquePeerId(bytes), owner))
+}
+
+/// A genesis allocation, such as a team or creator allocation, read from an allocations file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Allocation {
+	/// The SS58 address of the account.
+	pub account: AccountId,
+	/// The balance the account starts with.
+	#[serde(deserialize_with = "deserialize_balance")]
+	pub balance: Balance,
+	/// Locks all of `balance` but `liquid` and releases it linearly.
+	#[serde(default)]
+	pub vesting: Option<Vesting>,
+}
+
+/// A linear vesting schedule of an [`Allocation`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Vesting {
+	/// The block the balance starts to unlock at.
+	pub begin: BlockNumber,
+	/// The number of blocks over which the balance unlocks.
+	pub length: BlockNumber,
+	/// The part of the balance that is never locked.
+	#[serde(default, deserialize_with = "deserialize_balance")]
+	pub liquid: Balance,
 }
 
-pub fn development_config() -> Result<ChainSpec, String> {
+/// Deserializes a balance from an integer or, for balances too large for JSON or TOML integers,
+/// a decimal string.
+pub fn deserialize_balance<'de, D: Deserializer<'de>>(
+	deserializer: D,
+) -> Result<Balance, D::Error> {
+	struct BalanceVisitor;
+
+	impl<'de> de::Visitor<'de> for BalanceVisitor {
+		type Value = Balance;
+
+		fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			f.write_str("a non-negative integer or a decimal string")
+		}
+
+		fn visit_u64<E: de::Error>(self, v: u64) -> Result<Balance, E> {
+			Ok(v.into())
+		}
+
+		fn visit_i64<E: de::Error>(self, v: i64) -> Result<Balance, E> {
+			Balance::try_from(v).map_err(|_| E::custom(format!("negative balance {}", v)))
+		}
+
+		fn visit_str<E: de::Error>(self, v: &str) -> Result<Balance, E> {
+			v.parse().map_err(|_| E::custom(format!("invalid balance `{}`", v)))
+		}
+	}
+
+	deserializer.deserialize_any(BalanceVisitor)
+}
+
+/// Reads genesis allocations from a JSON file holding an array of [`Allocation`]s, e.g.
+///
+/// ```json
+/// [{ "account": "5EWw...", "balance": 1000000000000000, "vesting": { "begin": 0, "length": 100 } }]
+/// ```
+pub fn allocations_from_json_file(path: &Path) -> Result<Vec<Allocation>, String> {
+	let file = std::fs::File::open(path)
+		.map_err(|e| format!("Error opening allocations file `{}`: {}", path.display(), e))?;
+	serde_json::from_reader(std::io::BufReader::new(file))
+		.map_err(|e| format!("Error parsing allocations file `{}`: {}", path.display(), e))
+}
+
+/// The parts of the genesis that differ between chains.
+#[derive(Clone)]
+pub struct GenesisParams {
+	/// Validator accounts with their Aura and Grandpa session keys.
+	pub initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
+	/// The sudo key. Without one the chain is governed by the council from genesis.
+	pub root_key: Option<AccountId>,
+	/// Members of the council.
+	pub council: Vec<AccountId>,
+	/// Members of the technical committee.
+	pub technical_committee: Vec<AccountId>,
+	/// Accounts and the balances they start with.
+	pub endowments: Vec<(AccountId, Balance)>,
+	/// Allocations on top of `endowments`, optionally vesting.
+	pub allocations: Vec<Allocation>,
+	/// Nodes allowed by `pallet_node_authorization`, with their owners.
+	pub well_known_nodes: Vec<(OpaquePeerId, AccountId)>,
+	/// Accounts endowed with the Solar stable token, for testing.
+	pub stable_holders: Vec<AccountId>,
+}
+
+impl GenesisParams {
+	/// Checks what `pallet_balances`, `pallet_vesting` and the other genesis builders would
+	/// otherwise panic on, or what leaves the chain without a way to govern it.
+	pub fn validate(&self) -> Result<(), String> {
+		if self.initial_authorities.is_empty() {
+			return Err("At least one authority is required".into())
+		}
+		if self.root_key.is_none() && self.council.is_empty() {
+			return Err("Either a sudo key or a council is required".into())
+		}
+		unique("Authority", self.initial_authorities.iter().map(|a| &a.0))?;
+		// `pallet_session` panics at genesis on session keys shared by two validators.
+		unique("Aura key", self.initial_authorities.iter().map(|a| &a.1))?;
+		unique("GRANDPA key", self.initial_authorities.iter().map(|a| &a.2))?;
+		unique("Council member", self.council.iter())?;
+		unique("Technical committee member", self.technical_committee.iter())?;
+		unique(
+			"Well-known node",
+			self.well_known_nodes
+				.iter()
+				.map(|(peer_id, _)| bs58::encode(&peer_id.0).into_string()),
+		)?;
+		unique(
+			"Account",
+			self.endowments
+				.iter()
+				.map(|e| &e.0)
+				.chain(self.allocations.iter().map(|a| &a.account)),
+		)?;
+
+		for (account, balance) in &self.endowments {
+			if *balance < ExistentialDeposit::get() {
+				return Err(format!("Endowment of {} is below the existential deposit", account))
+			}
+		}
+		for Allocation { account, balance, vesting } in &self.allocations {
+			if *balance < ExistentialDeposit::get() {
+				return Err(format!("Allocation of {} is below the existential deposit", account))
+			}
+			if let Some(vesting) = vesting {
+				if vesting.length == 0 {
+					return Err(format!("Vesting of {} has a length of zero", account))
+				}
+				if vesting.liquid >= *balance {
+					return Err(format!("Vesting of {} locks nothing", account))
+				}
+				// `pallet_vesting` rejects schedules that unlock nothing per block.
+				if *balance - vesting.liquid < Balance::from(vesting.length) {
+					return Err(format!("Vesting of {} is longer than its locked balance", account))
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Fails on the first item of `items` that occurs more than once.
+fn unique<T: Ord + fmt::Debug>(
+	what: &str,
+	items: impl IntoIterator<Item = T>,
+) -> Result<(), String> {
+	let mut seen = BTreeSet::new();
+	for item in items {
+		if !seen.insert(item) {
+			return Err(format!("{} {:?} is listed more than once", what, item))
+		}
+	}
+	Ok(())
+}
+
+/// The genesis of the development and local testnet presets: Alice is the sudo key, the first
+/// three of the well-known development accounts form the council and the technical committee,
+/// and all of them are endowed with native and stable tokens.
+fn testnet_params(
+	initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
+	allocations: Vec<Allocation>,
+) -> Result<GenesisParams, String> {
+	let endowed_accounts = vec![
+		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		get_account_id_from_seed::<sr25519::Public>("Bob"),
+		get_account_id_from_seed::<sr25519::Public>("Charlie"),
+		get_account_id_from_seed::<sr25519::Public>("Dave"),
+		get_account_id_from_seed::<sr25519::Public>("Eve"),
+		get_account_id_from_seed::<sr25519::Public>("Ferdie"),
+		get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
+	];
+	let council: Vec<AccountId> = endowed_accounts.iter().take(3).cloned().collect();
+
+	let params = GenesisParams {
+		initial_authorities,
+		root_key: Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
+		technical_committee: council.clone(),
+		council,
+		endowments: endowed_accounts.iter().cloned().map(|k| (k, ENDOWMENT)).collect(),
+		allocations,
+		well_known_nodes: TESTNET_WELL_KNOWN_NODES
+			.iter()
+			.map(|(peer_id, owner)| well_known_node(peer_id, owner))
+			.collect::<Result<_, _>>()?,
+		stable_holders: endowed_accounts,
+	};
+	params.validate()?;
+	Ok(params)
+}
+
+pub fn development_config(allocations: Vec<Allocation>) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	let params = testnet_params(vec![authority_keys_from_seed("Alice")], allocations)?;
 
 	Ok(ChainSpec::from_genesis(
 		// Name
@@ -47,31 +330,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		// ID
 		"dev",
 		ChainType::Development,
-		move || {
-			testnet_genesis(
-				wasm_binary,
-				// Initial PoA authorities
-				vec![authority_keys_from_seed("Alice")],
-				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				// Pre-funded accounts
-				vec![
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
-					get_account_id_from_seed::<sr25519::Public>("Bob"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie"),
-					get_account_id_from_seed::<sr25519::Public>("Dave"),
-					get_account_id_from_seed::<sr25519::Public>("Eve"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-				],
-				true,
-			)
-		},
+		move || genesis_config(wasm_binary, params.clone()),
 		// Bootnodes
 		vec![],
 		// Telemetry
@@ -81,14 +340,18 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		// Fork ID
 		None,
 		// Properties
-		None,
+		Some(solar_properties()),
 		// Extensions
 		None,
 	))
 }
 
-pub fn local_testnet_config() -> Result<ChainSpec, String> {
+pub fn local_testnet_config(allocations: Vec<Allocation>) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	let params = testnet_params(
+		vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
+		allocations,
+	)?;
 
 	Ok(ChainSpec::from_genesis(
 		// Name
@@ -96,31 +359,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		// ID
 		"local_testnet",
 		ChainType::Local,
-		move || {
-			testnet_genesis(
-				wasm_binary,
-				// Initial PoA authorities
-				vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
-				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				// Pre-funded accounts
-				vec![
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
-					get_account_id_from_seed::<sr25519::Public>("Bob"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie"),
-					get_account_id_from_seed::<sr25519::Public>("Dave"),
-					get_account_id_from_seed::<sr25519::Public>("Eve"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-				],
-				true,
-			)
-		},
+		move || genesis_config(wasm_binary, params.clone()),
 		// Bootnodes
 		vec![],
 		// Telemetry
@@ -130,75 +369,101 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		// Fork ID
 		None,
 		// Properties
-		None,
+		Some(solar_properties()),
 		// Extensions
 		None,
 	))
 }
 
+/// The staging network, described by `node/res/staging.toml`.
+pub fn staging_config() -> Result<ChainSpec, String> {
+	GenesisDescription::from_toml(STAGING_GENESIS)
+		.and_then(GenesisDescription::into_chain_spec)
+		.map_err(|e| format!("Invalid staging genesis: {}", e))
+}
+
 /// Configure initial storage state for FRAME modules.
-fn testnet_genesis(
-	wasm_binary: &[u8],
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
-	root_key: AccountId,
-	endowed_accounts: Vec<AccountId>,
-	_enable_println: bool,
-) -> GenesisConfig {
+pub fn genesis_config(wasm_binary: &[u8], params: GenesisParams) -> GenesisConfig {
+	let GenesisParams {
+		initial_authorities,
+		root_key,
+		council,
+		technical_committee,
+		endowments,
+		allocations,
+		well_known_nodes,
+		stable_holders,
+	} = params;
+	let stable_owner = root_key
+		.clone()
+		.or_else(|| council.first().cloned())
+		.expect("validated to have a sudo key or a council; qed");
+
 	GenesisConfig {
 		system: SystemConfig {
 			// Add Wasm runtime to storage.
 			code: wasm_binary.to_vec(),
 		},
 		balances: BalancesConfig {
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+			balances: endowments
+				.into_iter()
+				.chain(allocations.iter().map(|a| (a.account.clone(), a.balance)))
+				.collect(),
 		},
-		aura: AuraConfig {
-			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
+		// Aura and Grandpa authorities are set by `Session` from the session keys below.
+		aura: AuraConfig { authorities: vec![] },
+		grandpa: GrandpaConfig { authorities: vec![] },
+		sudo: SudoConfig { key: root_key },
+		transaction_payment: Default::default(),
+		assets: solar_stable_genesis(stable_owner, &stable_holders),
+		node_authorization: NodeAuthorizationConfig { nodes: well_known_nodes },
+		nft: Default::default(),
+		// Trade commissions fund the treasury.
+		market: MarketConfig { organiser: Some(Treasury::account_id()), trade_commission: 20 },
+		contracts_filter: Default::default(),
+		council: Default::default(),
+		council_membership: CouncilMembershipConfig {
+			members: council,
+			phantom: Default::default(),
 		},
-		grandpa: GrandpaConfig {
-			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+		technical_committee: Default::default(),
+		technical_membership: TechnicalMembershipConfig {
+			members: technical_committee,
+			phantom: Default::default(),
 		},
-		sudo: SudoConfig { key: Some(root_key) },
-		transaction_payment: Default::default(),
-		node_authorization: NodeAuthorizationConfig {
-			nodes: vec![
-				(
-					OpaquePeerId(
-						bs58::decode("12D3KooWCySiYrhevMhVKXGZYEcUUViyti6wRSjXgnmN81D7g8ZY")
-							.into_vec()
-							.unwrap(),
-					),
-					AccountId32::from_str("5EWX1uk3P2wxLJSr5ZT3jSEPAPse67guqV5ASjU4yL89LN18")
-						.unwrap(),
-				),
-				(
-					OpaquePeerId(
-						bs58::decode("12D3KooWKHai5by8FAf5QmJMo53n1AfQzvtMvDwhzKtx4LRPL8Yn")
-							.into_vec()
-							.unwrap(),
-					),
-					AccountId32::from_str("5GHbL5YWjsaLL24PRAiX2YK8b3vcoqvStnMvRR75YrD5jTty")
-						.unwrap(),
-				),
-				(
-					OpaquePeerId(
-						bs58::decode("12D3KooWGHPNxMp3YwZYvDV7grbKsrCgKFEUSYjzjRRoKQ2JBT8S")
-							.into_vec()
-							.unwrap(),
-					),
-					AccountId32::from_str("5CDHFYGAaiK81vwaELU95fvDW8DYJkAkEqF5SVFwPzGjnSay")
-						.unwrap(),
-				),
-				(
-					OpaquePeerId(
-						bs58::decode("12D3KooWEU1k7baQEXDiXrxmmmq4Rj7kG7WKPZRm4Ru3hi11StN6")
-							.into_vec()
-							.unwrap(),
-					),
-					AccountId32::from_str("5G6KbEQTnQ5agwCWBqkij277wrAACJA37RJ157bUB842rExp")
-						.unwrap(),
-				),
-			],
+		democracy: Default::default(),
+		treasury: Default::default(),
+		// 5% inflation a year, minted to block authors.
+		block_reward: BlockRewardConfig { schedule: vec![(0, Perbill::from_percent(5))] },
+		validator_set: ValidatorSetConfig {
+			initial_validators: initial_authorities.iter().map(|x| x.0.clone()).collect(),
+		},
+		session: SessionConfig {
+			keys: initial_authorities
+				.into_iter()
+				.map(|(account, aura, grandpa)| {
+					(account.clone(), account, session_keys(aura, grandpa))
+				})
+				.collect(),
+		},
+		vesting: VestingConfig {
+			vesting: allocations
+				.into_iter()
+				.filter_map(|a| a.vesting.map(|v| (a.account, v.begin, v.length, v.liquid)))
+				.collect(),
 		},
 	}
 }
+
+/// The Solar stable token, sufficient so it can pay transaction fees, endowed to `holders`.
+///
+/// Fees are converted with the ratio of `min_balance` to the `ExistentialDeposit`, so using the
+/// existential deposit as `min_balance` prices the token one-to-one with the native token.
+fn solar_stable_genesis(owner: AccountId, holders: &[AccountId]) -> AssetsConfig {
+	let min_balance: Balance = ExistentialDeposit::get();
+	AssetsConfig {
+		assets: vec![(SOLAR_STABLE_ASSET_ID, owner, true, min_balance)],
+		metadata: vec![(SOLAR_STABLE_ASSET_ID, b"Solar USD".to_vec(), b"SUSD".to_vec(), 12)],
+		accounts: holders.iter().cloned().map(|k| (SOLAR_STABLE_ASSET_ID, k, ENDOWMENT)).collect(),
+	}
+}
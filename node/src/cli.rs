@@ -1,4 +1,8 @@
+use crate::chain_spec;
 use sc_cli::RunCmd;
+use solar_node_runtime::Balance;
+use sp_core::H256;
+use std::{path::PathBuf, str::FromStr};
 
 #[derive(Debug, clap::Parser)]
 pub struct Cli {
@@ -7,6 +11,238 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	/// JSON file of genesis allocations, optionally vesting, added to the dev or local chain.
+	#[clap(long, global = true, value_name = "PATH")]
+	pub allocations: Option<PathBuf>,
+
+	/// Seal blocks without Aura and Grandpa, for contract development: `instant` when a
+	/// transaction arrives, `manual` only through `engine_createBlock`, or `interval=<ms>`.
+	#[clap(long, value_name = "MODE")]
+	pub dev_seal: Option<Sealing>,
+
+	/// Index NFT, marketplace and contract events of finalized blocks into this SQLite database.
+	#[cfg(feature = "indexer")]
+	#[clap(long, value_name = "PATH")]
+	pub index_db: Option<PathBuf>,
+}
+
+/// How blocks are sealed with `--dev-seal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal blocks only when `engine_createBlock` is called.
+	Manual,
+	/// Seal a block every given number of milliseconds, even an empty one.
+	Interval(u64),
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Self::Instant),
+			"manual" => Ok(Self::Manual),
+			_ => match s.strip_prefix("interval=").map(str::parse) {
+				Some(Ok(millis)) if millis > 0 => Ok(Self::Interval(millis)),
+				_ => Err(format!(
+					"expected `instant`, `manual` or `interval=<ms>` with `ms` > 0, got `{}`",
+					s
+				)),
+			},
+		}
+	}
+}
+
+/// The `build-genesis` command.
+#[derive(Debug, clap::Parser)]
+pub struct BuildGenesisCmd {
+	/// The genesis description, a `.toml` or `.json` file like `node/res/staging.toml`.
+	#[clap(value_name = "FILE")]
+	pub file: PathBuf,
+
+	/// Print the raw genesis storage instead of the human-readable genesis config.
+	#[clap(long)]
+	pub raw: bool,
+}
+
+/// The `index` command.
+#[cfg(feature = "indexer")]
+#[derive(Debug, clap::Parser)]
+pub struct IndexCmd {
+	/// The SQLite database to write to, created if missing.
+	#[clap(long, value_name = "PATH")]
+	pub db: PathBuf,
+
+	/// The first block to index.
+	#[clap(long, value_name = "BLOCK", default_value = "0")]
+	pub from: solar_node_runtime::BlockNumber,
+
+	/// The last block to index, the last finalized block if missing.
+	#[clap(long, value_name = "BLOCK")]
+	pub to: Option<solar_node_runtime::BlockNumber>,
+
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[clap(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+#[cfg(feature = "indexer")]
+impl sc_cli::CliConfiguration for IndexCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// The `contract` commands.
+#[derive(Debug, clap::Subcommand)]
+pub enum ContractCmd {
+	/// Upload the code of a contract without instantiating it.
+	Upload(ContractUploadCmd),
+
+	/// Instantiate a contract, uploading its code unless `--code-hash` is given.
+	Instantiate(ContractInstantiateCmd),
+
+	/// Call a message of a contract in a transaction.
+	Call(ContractCallCmd),
+
+	/// Dry-run a message of a contract and print what it returns.
+	Query(ContractCallCmd),
+}
+
+/// The `contract upload` command.
+#[derive(Debug, clap::Parser)]
+pub struct ContractUploadCmd {
+	/// The `.contract` bundle built by `cargo contract`, holding the code and metadata.
+	#[clap(long, value_name = "PATH")]
+	pub bundle: PathBuf,
+
+	#[clap(flatten)]
+	pub tx: ContractTxParams,
+}
+
+/// The `contract instantiate` command.
+#[derive(Debug, clap::Parser)]
+pub struct ContractInstantiateCmd {
+	/// The `.contract` bundle, or only its metadata with `--code-hash`.
+	#[clap(long, value_name = "PATH")]
+	pub bundle: PathBuf,
+
+	/// Instantiate code uploaded before instead of the code of the bundle.
+	#[clap(long, value_name = "HASH")]
+	pub code_hash: Option<H256>,
+
+	/// The constructor to call.
+	#[clap(long, value_name = "NAME", default_value = "new")]
+	pub constructor: String,
+
+	/// Constructor arguments as JSON values, such as `true`, `[1, 2]` or `{"Some": 1}`. Other
+	/// text, like addresses and numbers, is taken as a string.
+	#[clap(long, value_name = "ARG", multiple_values = true, allow_hyphen_values = true)]
+	pub args: Vec<String>,
+
+	/// Balance transferred to the new contract.
+	#[clap(long, value_name = "BALANCE", default_value = "0")]
+	pub value: Balance,
+
+	/// Hex salt that makes the contract address unique.
+	#[clap(long, value_name = "HEX", default_value = "0x")]
+	pub salt: String,
+
+	#[clap(flatten)]
+	pub tx: ContractTxParams,
+}
+
+/// The `contract call` and `contract query` commands.
+#[derive(Debug, clap::Parser)]
+pub struct ContractCallCmd {
+	/// SS58 address of the contract.
+	#[clap(long, value_name = "SS58")]
+	pub contract: String,
+
+	/// The `.contract` bundle or the `metadata.json` of the contract.
+	#[clap(long, value_name = "PATH")]
+	pub bundle: PathBuf,
+
+	/// The message to call, with or without its trait, such as `transfer`.
+	#[clap(long, value_name = "NAME")]
+	pub message: String,
+
+	/// Message arguments, like those of `contract instantiate --args`.
+	#[clap(long, value_name = "ARG", multiple_values = true, allow_hyphen_values = true)]
+	pub args: Vec<String>,
+
+	/// Balance transferred to the contract.
+	#[clap(long, value_name = "BALANCE", default_value = "0")]
+	pub value: Balance,
+
+	#[clap(flatten)]
+	pub tx: ContractTxParams,
+}
+
+/// How the `contract` commands reach the node and sign their transactions.
+#[derive(Debug, clap::Parser)]
+pub struct ContractTxParams {
+	/// HTTP RPC endpoint of the node.
+	#[clap(long, value_name = "URL", default_value = "http://127.0.0.1:9933")]
+	pub url: String,
+
+	/// Secret URI of the sr25519 signing key, such as `//Alice` or a mnemonic.
+	#[clap(long, value_name = "SURI", conflicts_with = "keystore_path")]
+	pub suri: Option<String>,
+
+	/// Keystore holding the signing key given with `--account`.
+	#[clap(long, value_name = "PATH", requires = "account")]
+	pub keystore_path: Option<PathBuf>,
+
+	/// SS58 address of the signing key in `--keystore-path`, or the caller of a query.
+	#[clap(long, value_name = "SS58")]
+	pub account: Option<String>,
+
+	/// Key type of the signing key in `--keystore-path`.
+	#[clap(long, value_name = "ID", default_value = "acco")]
+	pub key_type: String,
+
+	/// Password of the `--suri` key or of the keystore.
+	#[clap(long)]
+	pub password: Option<String>,
+
+	/// Gas limit of the transaction, the gas its dry-run required if missing.
+	#[clap(long, value_name = "GAS")]
+	pub gas_limit: Option<u64>,
+
+	/// The most balance that may be reserved for storage, unlimited if missing.
+	#[clap(long, value_name = "BALANCE")]
+	pub storage_deposit_limit: Option<Balance>,
+
+	/// Only dry-run the transaction instead of submitting it.
+	#[clap(long)]
+	pub dry_run: bool,
+}
+
+impl Cli {
+	/// The genesis allocations given with `--allocations`, if any.
+	pub fn allocations(&self) -> Result<Vec<chain_spec::Allocation>, String> {
+		match &self.allocations {
+			Some(path) => chain_spec::allocations_from_json_file(path),
+			None => Ok(Vec::new()),
+		}
+	}
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -18,6 +254,9 @@ pub enum Subcommand {
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
 
+	/// Build a chain specification from a TOML or JSON genesis description.
+	BuildGenesis(BuildGenesisCmd),
+
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
 
@@ -50,4 +289,16 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Upload, instantiate, call and query ink! contracts over RPC.
+	#[clap(subcommand)]
+	Contract(ContractCmd),
+
+	/// Index NFT, marketplace and contract events of a range of blocks into SQLite.
+	#[cfg(feature = "indexer")]
+	Index(IndexCmd),
+
+	/// Index events into SQLite. Note: `indexer` feature must be enabled.
+	#[cfg(not(feature = "indexer"))]
+	Index,
 }
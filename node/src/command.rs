@@ -2,13 +2,18 @@ use crate::{
 	chain_spec,
 	cli::{Cli, Subcommand},
 	command_helper::{inherent_benchmark_data, BenchmarkExtrinsicBuilder},
+	genesis::GenesisDescription,
 	service,
 	service::ExecutorDispatch,
 };
 use frame_benchmarking_cli::{BenchmarkCmd, SUBSTRATE_REFERENCE_HARDWARE};
 use sc_cli::{ChainSpec, RuntimeVersion, SubstrateCli};
 use sc_service::PartialComponents;
-use solar_node_runtime::Block;
+use solar_node_runtime::{Block, SS58Prefix};
+use sp_core::{
+	crypto::{set_default_ss58_version, Ss58AddressFormat},
+	Get,
+};
 use std::sync::Arc;
 
 impl SubstrateCli for Cli {
@@ -38,8 +43,11 @@ impl SubstrateCli for Cli {
 
 	fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
 		Ok(match id {
-			"" | "dev" => Box::new(chain_spec::development_config()?),
-			"local" => Box::new(chain_spec::local_testnet_config()?),
+			"" | "dev" => Box::new(chain_spec::development_config(self.allocations()?)?),
+			"local" => Box::new(chain_spec::local_testnet_config(self.allocations()?)?),
+			"staging" => Box::new(chain_spec::staging_config()?),
+			_ if self.allocations.is_some() =>
+				return Err("--allocations only applies to the dev and local chains".into()),
 			path =>
 				Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
 		})
@@ -52,6 +60,10 @@ impl SubstrateCli for Cli {
 
 /// Parse and run command line arguments
 pub fn run() -> sc_cli::Result<()> {
+	// Addresses are printed and parsed in the Solarchain format, also by subcommands such as
+	// `key` that run without a chain spec to take the format from.
+	set_default_ss58_version(Ss58AddressFormat::custom(SS58Prefix::get()));
+
 	let cli = Cli::from_args();
 
 	match &cli.subcommand {
@@ -60,6 +72,11 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
 		},
+		Some(Subcommand::BuildGenesis(cmd)) => {
+			let spec = GenesisDescription::from_file(&cmd.file)?.into_chain_spec()?;
+			println!("{}", sc_service::chain_ops::build_spec(&spec, cmd.raw)?);
+			Ok(())
+		},
 		Some(Subcommand::CheckBlock(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -164,10 +181,28 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run::<Block>(&config))
 		},
+		Some(Subcommand::Contract(cmd)) => cmd.run(),
+		#[cfg(feature = "indexer")]
+		Some(Subcommand::Index(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, .. } = service::new_partial(&config)?;
+				cmd.run(client, service::contract_metadata(&config)?)
+			})
+		},
+		#[cfg(not(feature = "indexer"))]
+		Some(Subcommand::Index) => Err("The indexer wasn't enabled when building the node. \
+				You can enable it with `--features indexer`."
+			.into()),
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
+			let dev_seal = cli.dev_seal;
+			#[cfg(feature = "indexer")]
+			let index_db = cli.index_db.clone();
+			#[cfg(not(feature = "indexer"))]
+			let index_db = None;
 			runner.run_node_until_exit(|config| async move {
-				service::new_full(config).map_err(sc_cli::Error::Service)
+				service::new_full(config, dev_seal, index_db).map_err(sc_cli::Error::Service)
 			})
 		},
 	}
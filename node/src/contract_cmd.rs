@@ -0,0 +1,431 @@
+//! The `contract` commands, which upload, instantiate, call and query ink! contracts through the
+//! RPC endpoint of a running node.
+//!
+//! Constructor and message arguments are encoded with the metadata of the contract's `.contract`
+//! bundle. Every transaction is dry-run first with the `contracts_*` RPC methods, which call the
+//! `ContractsApi` of the runtime, to find its gas limit and fail early. Transactions are signed
+//! with an sr25519 key from `--suri` or from a local keystore, and the command prints a JSON
+//! summary of what happened.
+
+use std::{fs, path::Path};
+
+use codec::Encode;
+use jsonrpsee::{
+	core::client::ClientT,
+	http_client::{HttpClient, HttpClientBuilder},
+	types::ParamsSer,
+};
+use sc_keystore::LocalKeystore;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use solar_node_runtime::{self as runtime, AccountId, Balance, RuntimeBlockWeights};
+use sp_core::{
+	crypto::{CryptoTypePublicPair, KeyTypeId, SecretString, Ss58Codec},
+	sr25519, Bytes, Pair, H256,
+};
+use sp_keystore::SyncCryptoStore;
+use sp_runtime::generic::Era;
+
+use crate::{
+	cli::{
+		ContractCallCmd, ContractCmd, ContractInstantiateCmd, ContractTxParams, ContractUploadCmd,
+	},
+	contract_metadata::{CallSpec, ContractAbi},
+};
+
+/// The `ReturnFlags` bit set when a contract reverted.
+const REVERT: u64 = 1;
+
+impl ContractCmd {
+	/// Runs the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let summary = match self {
+			ContractCmd::Upload(cmd) => cmd.run()?,
+			ContractCmd::Instantiate(cmd) => cmd.run()?,
+			ContractCmd::Call(cmd) => cmd.run(true)?,
+			ContractCmd::Query(cmd) => cmd.run(false)?,
+		};
+		println!("{}", serde_json::to_string_pretty(&summary).map_err(|e| e.to_string())?);
+		Ok(())
+	}
+}
+
+impl ContractUploadCmd {
+	fn run(&self) -> Result<Value, String> {
+		let bundle = Bundle::open(&self.bundle)?;
+		let code = bundle.code()?;
+		let rpc = Rpc::connect(&self.tx.url)?;
+		let signer = self.tx.signer()?.ok_or("signing needs `--suri` or `--keystore-path`")?;
+
+		let result: Value = rpc.request(
+			"contracts_upload_code",
+			vec![json!({
+				"origin": signer.account(),
+				"code": Bytes(code.to_vec()),
+				"storageDepositLimit": self.tx.storage_deposit_limit.map(number_or_hex),
+			})],
+		)?;
+		let uploaded = outcome(&result, &result)?;
+		let mut summary = json!({
+			"codeHash": uploaded["codeHash"],
+			"deposit": uploaded["deposit"],
+		});
+		if !self.tx.dry_run {
+			let call = pallet_contracts::Call::<runtime::Runtime>::upload_code {
+				code: code.to_vec(),
+				storage_deposit_limit: self.tx.storage_deposit_limit.map(Into::into),
+			};
+			summary["extrinsic"] = json!(rpc.submit(&signer, call.into())?);
+		}
+		Ok(summary)
+	}
+}
+
+impl ContractInstantiateCmd {
+	fn run(&self) -> Result<Value, String> {
+		let bundle = Bundle::open(&self.bundle)?;
+		let constructor = bundle.abi.constructor(&self.constructor)?;
+		let data = bundle.abi.encode_call(constructor, &parse_args(&self.args))?;
+		let salt = sp_core::bytes::from_hex(&self.salt)
+			.map_err(|e| format!("invalid --salt `{}`: {}", self.salt, e))?;
+		let rpc = Rpc::connect(&self.tx.url)?;
+		let signer = self.tx.signer()?.ok_or("signing needs `--suri` or `--keystore-path`")?;
+
+		let code = match self.code_hash {
+			Some(code_hash) => json!({ "existing": code_hash }),
+			None => json!({ "upload": Bytes(bundle.code()?.to_vec()) }),
+		};
+		let result: Value = rpc.request(
+			"contracts_instantiate",
+			vec![json!({
+				"origin": signer.account(),
+				"value": number_or_hex(self.value),
+				"gasLimit": self.tx.dry_run_gas_limit(),
+				"storageDepositLimit": self.tx.storage_deposit_limit.map(number_or_hex),
+				"code": code,
+				"data": Bytes(data.clone()),
+				"salt": Bytes(salt.clone()),
+			})],
+		)?;
+		let instantiated = outcome(&result, &result["result"])?;
+		check_reverted(&bundle.abi, &instantiated["result"], &constructor.name(), None)?;
+
+		let mut summary = json!({
+			"contract": instantiated["accountId"],
+			"gasRequired": result["gasRequired"],
+			"storageDeposit": result["storageDeposit"],
+		});
+		if !self.tx.dry_run {
+			let gas_limit = self.tx.gas_limit(&result)?;
+			let storage_deposit_limit = self.tx.storage_deposit_limit.map(Into::into);
+			let call = match self.code_hash {
+				Some(code_hash) => pallet_contracts::Call::<runtime::Runtime>::instantiate {
+					value: self.value,
+					gas_limit,
+					storage_deposit_limit,
+					code_hash,
+					data,
+					salt,
+				},
+				None => pallet_contracts::Call::<runtime::Runtime>::instantiate_with_code {
+					value: self.value,
+					gas_limit,
+					storage_deposit_limit,
+					code: bundle.code()?.to_vec(),
+					data,
+					salt,
+				},
+			};
+			summary["extrinsic"] = json!(rpc.submit(&signer, call.into())?);
+		}
+		Ok(summary)
+	}
+}
+
+impl ContractCallCmd {
+	/// Dry-runs the message, then submits it as a transaction if `submit` is set and the message
+	/// may change state.
+	fn run(&self, submit: bool) -> Result<Value, String> {
+		let bundle = Bundle::open(&self.bundle)?;
+		let message = bundle.abi.message(&self.message)?;
+		let data = bundle.abi.encode_call(message, &parse_args(&self.args))?;
+		let contract = parse_account(&self.contract, "--contract")?;
+		let rpc = Rpc::connect(&self.tx.url)?;
+		let signer = self.tx.signer()?;
+		let origin = match (&signer, self.tx.account()?) {
+			(Some(signer), _) => signer.account(),
+			(None, Some(account)) if !submit => account,
+			_ if submit => return Err("signing needs `--suri` or `--keystore-path`".into()),
+			_ => return Err("a query needs `--account`, `--suri` or `--keystore-path`".into()),
+		};
+
+		let result: Value = rpc.request(
+			"contracts_call",
+			vec![json!({
+				"origin": origin,
+				"dest": contract,
+				"value": number_or_hex(self.value),
+				"gasLimit": self.tx.dry_run_gas_limit(),
+				"storageDepositLimit": self.tx.storage_deposit_limit.map(number_or_hex),
+				"inputData": Bytes(data.clone()),
+			})],
+		)?;
+		let returned = outcome(&result, &result["result"])?;
+		let value = check_reverted(&bundle.abi, returned, &message.name(), Some(message))?;
+
+		let mut summary = json!({
+			"returned": value,
+			"gasRequired": result["gasRequired"],
+			"storageDeposit": result["storageDeposit"],
+		});
+		if submit && !message.mutates() {
+			log::warn!("`{}` does not change state, so it was only dry-run", message.name());
+		} else if submit && !self.tx.dry_run {
+			let signer = signer.expect("submitting needs a signer, checked above; qed");
+			let call = pallet_contracts::Call::<runtime::Runtime>::call {
+				dest: contract.into(),
+				value: self.value,
+				gas_limit: self.tx.gas_limit(&result)?,
+				storage_deposit_limit: self.tx.storage_deposit_limit.map(Into::into),
+				data,
+			};
+			summary["extrinsic"] = json!(rpc.submit(&signer, call.into())?);
+		}
+		Ok(summary)
+	}
+}
+
+/// A `.contract` bundle, or a `metadata.json` without the code.
+struct Bundle {
+	abi: ContractAbi,
+	code: Option<Vec<u8>>,
+}
+
+impl Bundle {
+	fn open(path: &Path) -> Result<Self, String> {
+		let content = fs::read_to_string(path)
+			.map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+		let metadata: Value = serde_json::from_str(&content)
+			.map_err(|e| format!("Unable to parse {}: {}", path.display(), e))?;
+		let code = metadata
+			.pointer("/source/wasm")
+			.and_then(Value::as_str)
+			.map(sp_core::bytes::from_hex)
+			.transpose()
+			.map_err(|e| format!("invalid `source.wasm` in {}: {}", path.display(), e))?;
+		Ok(Self { abi: ContractAbi::from_metadata(&metadata)?, code })
+	}
+
+	fn code(&self) -> Result<&[u8], String> {
+		self.code
+			.as_deref()
+			.ok_or_else(|| "the bundle has no `source.wasm`, pass the `.contract` file".into())
+	}
+}
+
+/// The key transactions are signed with.
+enum Signer {
+	Pair(sr25519::Pair),
+	Keystore { keystore: LocalKeystore, key_type: KeyTypeId, public: sr25519::Public },
+}
+
+impl Signer {
+	fn account(&self) -> AccountId {
+		match self {
+			Signer::Pair(pair) => pair.public().into(),
+			Signer::Keystore { public, .. } => (*public).into(),
+		}
+	}
+
+	fn sign(&self, payload: &[u8]) -> Result<sr25519::Signature, String> {
+		match self {
+			Signer::Pair(pair) => Ok(pair.sign(payload)),
+			Signer::Keystore { keystore, key_type, public } => {
+				let key = CryptoTypePublicPair(sr25519::CRYPTO_ID, public.0.to_vec());
+				let signature = SyncCryptoStore::sign_with(keystore, *key_type, &key, payload)
+					.map_err(|e| format!("Signing failed: {}", e))?
+					.ok_or("the signing key is not in the keystore")?;
+				sr25519::Signature::try_from(&signature[..])
+					.map_err(|_| "the keystore returned an invalid signature".into())
+			},
+		}
+	}
+}
+
+impl ContractTxParams {
+	/// The signer given with `--suri` or `--keystore-path`, if any.
+	fn signer(&self) -> Result<Option<Signer>, String> {
+		if let Some(suri) = &self.suri {
+			let pair = sr25519::Pair::from_string(suri, self.password.as_deref())
+				.map_err(|e| format!("invalid --suri: {:?}", e))?;
+			return Ok(Some(Signer::Pair(pair)))
+		}
+		let path = match &self.keystore_path {
+			Some(path) => path,
+			None => return Ok(None),
+		};
+		let account = self.account()?.expect("`--keystore-path` requires `--account`; qed");
+		let keystore =
+			LocalKeystore::open(path.clone(), self.password.clone().map(SecretString::new))
+				.map_err(|e| format!("Unable to open the keystore {}: {}", path.display(), e))?;
+		let key_type = KeyTypeId::try_from(self.key_type.as_str())
+			.map_err(|_| format!("--key-type must be 4 characters, got `{}`", self.key_type))?;
+		let public = sr25519::Public::from_raw(account.clone().into());
+		if !SyncCryptoStore::has_keys(&keystore, &[(public.0.to_vec(), key_type)]) {
+			return Err(format!("no {} key of {} in {}", self.key_type, account, path.display()))
+		}
+		Ok(Some(Signer::Keystore { keystore, key_type, public }))
+	}
+
+	fn account(&self) -> Result<Option<AccountId>, String> {
+		self.account
+			.as_deref()
+			.map(|account| parse_account(account, "--account"))
+			.transpose()
+	}
+
+	/// The gas limit of dry-runs, `--gas-limit` or else as much as a block may use.
+	fn dry_run_gas_limit(&self) -> Value {
+		json!(self.gas_limit.unwrap_or_else(|| RuntimeBlockWeights::get().max_block))
+	}
+
+	/// The gas limit of a transaction, `--gas-limit` or else what its dry-run required.
+	fn gas_limit(&self, dry_run: &Value) -> Result<u64, String> {
+		match self.gas_limit {
+			Some(gas_limit) => Ok(gas_limit),
+			None => dry_run["gasRequired"]
+				.as_u64()
+				.ok_or_else(|| format!("the dry-run returned no `gasRequired`: {}", dry_run)),
+		}
+	}
+}
+
+/// An HTTP RPC client of the node.
+struct Rpc {
+	client: HttpClient,
+	runtime: tokio::runtime::Runtime,
+}
+
+impl Rpc {
+	fn connect(url: &str) -> Result<Self, String> {
+		let client = HttpClientBuilder::default().build(url).map_err(|e| e.to_string())?;
+		let runtime = tokio::runtime::Builder::new_current_thread()
+			.enable_all()
+			.build()
+			.map_err(|e| e.to_string())?;
+		Ok(Self { client, runtime })
+	}
+
+	fn request<T: DeserializeOwned>(&self, method: &str, params: Vec<Value>) -> Result<T, String> {
+		self.runtime
+			.block_on(self.client.request(method, Some(ParamsSer::Array(params))))
+			.map_err(|e| format!("{} failed: {}", method, e))
+	}
+
+	/// Signs `call` as the next transaction of `signer`, submits it and returns its hash.
+	fn submit(&self, signer: &Signer, call: runtime::Call) -> Result<H256, String> {
+		let genesis_hash: H256 = self.request("chain_getBlockHash", vec![json!(0)])?;
+		let version: Value = self.request("state_getRuntimeVersion", vec![])?;
+		let version_field = |field: &str| {
+			version[field]
+				.as_u64()
+				.map(|version| version as u32)
+				.ok_or_else(|| format!("the node returned no `{}`", field))
+		};
+		let spec_version = version_field("specVersion")?;
+		let transaction_version = version_field("transactionVersion")?;
+		let nonce: u32 = self.request("system_accountNextIndex", vec![json!(signer.account())])?;
+
+		let extra: runtime::SignedExtra = (
+			frame_system::CheckNonZeroSender::<runtime::Runtime>::new(),
+			frame_system::CheckSpecVersion::<runtime::Runtime>::new(),
+			frame_system::CheckTxVersion::<runtime::Runtime>::new(),
+			frame_system::CheckGenesis::<runtime::Runtime>::new(),
+			frame_system::CheckEra::<runtime::Runtime>::from(Era::Immortal),
+			frame_system::CheckNonce::<runtime::Runtime>::from(nonce),
+			frame_system::CheckWeight::<runtime::Runtime>::new(),
+			pallet_asset_tx_payment::ChargeAssetTxPayment::<runtime::Runtime>::from(0, None),
+		);
+		let payload = runtime::SignedPayload::from_raw(
+			call.clone(),
+			extra.clone(),
+			((), spec_version, transaction_version, genesis_hash, genesis_hash, (), (), ()),
+		);
+		let signature = payload.using_encoded(|payload| signer.sign(payload))?;
+		let extrinsic = runtime::UncheckedExtrinsic::new_signed(
+			call,
+			signer.account().into(),
+			runtime::Signature::Sr25519(signature),
+			extra,
+		);
+		self.request("author_submitExtrinsic", vec![json!(Bytes(extrinsic.encode()))])
+	}
+}
+
+/// The `Ok` value of the dry-run `result`, or its error with the contract's debug message.
+fn outcome<'a>(dry_run: &Value, result: &'a Value) -> Result<&'a Value, String> {
+	if let Some(ok) = result.get("Ok") {
+		return Ok(ok)
+	}
+	let mut error = format!("the dry-run failed: {}", result.get("Err").unwrap_or(result));
+	if let Some(message) = debug_message(dry_run) {
+		error += &format!(", debug message: {}", message);
+	}
+	Err(error)
+}
+
+/// Fails if the execution `result` of a constructor or message reverted, and decodes what a
+/// `message` returned.
+fn check_reverted(
+	abi: &ContractAbi,
+	result: &Value,
+	name: &str,
+	message: Option<&CallSpec>,
+) -> Result<Value, String> {
+	let flags = &result["flags"];
+	let flags = flags.get("bits").unwrap_or(flags).as_u64().unwrap_or_default();
+	let data = result["data"]
+		.as_str()
+		.map(sp_core::bytes::from_hex)
+		.transpose()
+		.map_err(|e| format!("invalid data returned by `{}`: {}", name, e))?
+		.unwrap_or_default();
+	let returned = match message {
+		Some(message) => abi.decode_return(message, &data),
+		None => Ok(Value::Null),
+	};
+	if flags & REVERT != 0 {
+		let returned = returned.unwrap_or_else(|_| json!(Bytes(data)));
+		return Err(format!("`{}` reverted, returning {}", name, returned))
+	}
+	returned.map_err(|e| format!("Unable to decode what `{}` returned: {}", name, e))
+}
+
+/// The UTF-8 debug message of a dry-run, if any.
+fn debug_message(dry_run: &Value) -> Option<String> {
+	let bytes = dry_run["debugMessage"]
+		.as_str()
+		.and_then(|hex| sp_core::bytes::from_hex(hex).ok())?;
+	(!bytes.is_empty()).then(|| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Command line arguments as JSON values. Numbers are kept as text, so that balances beyond
+/// the range of JSON numbers stay exact, and anything that is not JSON is taken as text.
+fn parse_args(args: &[String]) -> Vec<Value> {
+	args.iter()
+		.map(|arg| match serde_json::from_str(arg) {
+			Ok(Value::Number(_)) | Err(_) => Value::String(arg.clone()),
+			Ok(value) => value,
+		})
+		.collect()
+}
+
+fn parse_account(address: &str, flag: &str) -> Result<AccountId, String> {
+	AccountId::from_ss58check(address)
+		.map_err(|e| format!("invalid {} `{}`: {:?}", flag, address, e))
+}
+
+/// A balance the way the node's RPC takes it, a hex string.
+fn number_or_hex(balance: Balance) -> Value {
+	json!(format!("0x{:x}", balance))
+}
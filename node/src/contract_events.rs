@@ -0,0 +1,286 @@
+//! RPC methods to register ink! metadata and decode the contract events of a block with it.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::{Compact, Decode};
+use frame_system::{EventRecord, Phase};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_client_api::{Backend, StorageProvider};
+use sc_rpc_api::DenyUnsafe;
+use serde::Serialize;
+use serde_json::Value;
+use solar_node_runtime::{opaque::Block, AccountId, Event, Hash};
+use sp_blockchain::HeaderBackend;
+use sp_core::{storage::StorageKey, twox_128, twox_64, Bytes};
+use sp_runtime::{generic::BlockId, traits::Header};
+
+use crate::contract_metadata::{DecodedEvent, MetadataStore};
+
+#[rpc(server)]
+pub trait ContractEventsApi<BlockHash> {
+	/// Registers the ink! metadata JSON of the contract code with `code_hash`.
+	#[method(name = "solar_registerContractMetadata")]
+	fn register_contract_metadata(&self, code_hash: Hash, metadata: Value) -> RpcResult<()>;
+
+	/// The contract events of block `at`, decoded where metadata is registered.
+	#[method(name = "solar_decodeContractEvents")]
+	fn decode_contract_events(&self, at: Option<BlockHash>) -> RpcResult<Vec<ContractEvent>>;
+}
+
+/// A `ContractEmitted` event of a block.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractEvent {
+	/// Index of the event among all events of the block.
+	pub event_index: u32,
+	/// Index of the extrinsic that emitted the event.
+	pub extrinsic_index: Option<u32>,
+	/// The contract that emitted the event.
+	pub contract: AccountId,
+	/// Code hash of the contract, if it still exists.
+	pub code_hash: Option<Hash>,
+	/// Topics of the event.
+	pub topics: Vec<Hash>,
+	/// The raw event data.
+	pub data: Bytes,
+	/// The decoded event, if metadata is registered for the contract code.
+	pub decoded: Option<DecodedEvent>,
+	/// Why the event could not be decoded.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// Reading the storage of the block failed.
+	StorageError,
+	/// The block was not found.
+	UnknownBlock,
+	/// The metadata could not be registered.
+	InvalidMetadata,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::StorageError => 1,
+			Error::UnknownBlock => 2,
+			Error::InvalidMetadata => 3,
+		}
+	}
+}
+
+fn rpc_error(error: Error, message: &str, data: Option<String>) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(error.into(), message.to_string(), data)).into()
+}
+
+/// Provides RPC methods to decode contract events.
+pub struct ContractEvents<C, B> {
+	client: Arc<C>,
+	store: Arc<MetadataStore>,
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> ContractEvents<C, B> {
+	/// Creates a new instance of the contract events RPC.
+	pub fn new(client: Arc<C>, store: Arc<MetadataStore>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, store, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, B> ContractEventsApiServer<Hash> for ContractEvents<C, B>
+where
+	C: StorageProvider<Block, B> + HeaderBackend<Block> + Send + Sync + 'static,
+	B: Backend<Block> + Send + Sync + 'static,
+{
+	fn register_contract_metadata(&self, code_hash: Hash, metadata: Value) -> RpcResult<()> {
+		self.deny_unsafe.check_if_safe()?;
+		self.store.register(code_hash, &metadata).map_err(|e| {
+			rpc_error(Error::InvalidMetadata, "Unable to register the metadata.", Some(e))
+		})
+	}
+
+	fn decode_contract_events(&self, at: Option<Hash>) -> RpcResult<Vec<ContractEvent>> {
+		let storage_error =
+			|e: String| rpc_error(Error::StorageError, "Unable to read the events.", Some(e));
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let header = self
+			.client
+			.header(BlockId::Hash(hash))
+			.map_err(|e| storage_error(e.to_string()))?
+			.ok_or_else(|| rpc_error(Error::UnknownBlock, "Block not found.", None))?;
+
+		let mut events = Vec::new();
+		for (event_index, record) in block_events(&*self.client, hash)
+			.map_err(storage_error)?
+			.into_iter()
+			.enumerate()
+		{
+			let (contract, data) = match record.event {
+				Event::Contracts(pallet_contracts::Event::ContractEmitted { contract, data }) =>
+					(contract, data),
+				_ => continue,
+			};
+			let code_hash = code_hash(&*self.client, hash, *header.parent_hash(), &contract)
+				.map_err(storage_error)?;
+			let decoded = match code_hash {
+				None => Err("the contract does not exist".to_string()),
+				Some(code_hash) => self.store.decode_event(&code_hash, &data),
+			};
+			events.push(ContractEvent {
+				event_index: event_index as u32,
+				extrinsic_index: extrinsic_index(&record.phase),
+				contract,
+				code_hash,
+				topics: record.topics,
+				data: data.into(),
+				error: decoded.as_ref().err().cloned(),
+				decoded: decoded.ok(),
+			});
+		}
+		Ok(events)
+	}
+}
+
+/// Reads and decodes the storage value under `key` at block `hash`.
+fn storage<C, B, T>(client: &C, hash: Hash, key: Vec<u8>) -> Result<Option<T>, String>
+where
+	C: StorageProvider<Block, B>,
+	B: Backend<Block>,
+	T: Decode,
+{
+	let value = client
+		.storage(&BlockId::Hash(hash), &StorageKey(key))
+		.map_err(|e| e.to_string())?;
+	value
+		.map(|data| T::decode(&mut &data.0[..]))
+		.transpose()
+		.map_err(|e| e.to_string())
+}
+
+/// The events of block `hash`, up to the first one this node cannot decode.
+pub fn block_events<C, B>(client: &C, hash: Hash) -> Result<Vec<EventRecord<Event, Hash>>, String>
+where
+	C: StorageProvider<Block, B>,
+	B: Backend<Block>,
+{
+	let key = [twox_128(b"System"), twox_128(b"Events")].concat();
+	let events = client
+		.storage(&BlockId::Hash(hash), &StorageKey(key))
+		.map_err(|e| e.to_string())?;
+	let (records, error) = events.map(|events| decode_events(&events.0)).unwrap_or_default();
+	if let Some((index, e)) = error {
+		log::warn!(
+			target: "rpc",
+			"Skipping the events of block {} from #{} on, which cannot be decoded: {}",
+			hash,
+			index,
+			e,
+		);
+	}
+	Ok(records)
+}
+
+/// Decodes the records of `System::Events` one at a time.
+///
+/// The length of a record is only known once it is decoded, so the records after one that cannot
+/// be decoded, e.g. an event of a pallet added by a later runtime, cannot be found either. The
+/// records before it are returned along with its index and the error.
+fn decode_events(mut input: &[u8]) -> (Vec<EventRecord<Event, Hash>>, Option<(u32, String)>) {
+	let count = match Compact::<u32>::decode(&mut input) {
+		Ok(Compact(count)) => count,
+		Err(e) => return (Vec::new(), Some((0, e.to_string()))),
+	};
+	let mut records = Vec::new();
+	for index in 0..count {
+		match EventRecord::decode(&mut input) {
+			Ok(record) => records.push(record),
+			Err(e) => return (records, Some((index, e.to_string()))),
+		}
+	}
+	(records, None)
+}
+
+/// The code hash of `contract` at block `hash`, or at its parent `parent_hash` if the contract
+/// was terminated in the block.
+pub fn code_hash<C, B>(
+	client: &C,
+	hash: Hash,
+	parent_hash: Hash,
+	contract: &AccountId,
+) -> Result<Option<Hash>, String>
+where
+	C: StorageProvider<Block, B>,
+	B: Backend<Block>,
+{
+	let key = [
+		&twox_128(b"Contracts")[..],
+		&twox_128(b"ContractInfoOf")[..],
+		&twox_64(contract.as_ref())[..],
+		contract.as_ref(),
+	]
+	.concat();
+	// `ContractInfo` starts with the trie ID and the code hash.
+	for at in [hash, parent_hash] {
+		if let Some((_, code_hash)) = storage::<_, _, (Vec<u8>, Hash)>(client, at, key.clone())? {
+			return Ok(Some(code_hash))
+		}
+	}
+	Ok(None)
+}
+
+/// The index of the extrinsic an event was emitted by, if any.
+pub fn extrinsic_index(phase: &Phase) -> Option<u32> {
+	match phase {
+		Phase::ApplyExtrinsic(index) => Some(*index),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+
+	fn record(index: u32) -> EventRecord<Event, Hash> {
+		EventRecord {
+			phase: Phase::ApplyExtrinsic(index),
+			event: Event::System(frame_system::Event::NewAccount {
+				account: AccountId::new([index as u8; 32]),
+			}),
+			topics: vec![],
+		}
+	}
+
+	#[test]
+	fn decodes_all_events() {
+		let records = vec![record(0), record(1)];
+		assert_eq!(decode_events(&records.encode()), (records, None));
+	}
+
+	#[test]
+	fn keeps_the_events_before_an_undecodable_one() {
+		let mut data = Compact(3u32).encode();
+		data.extend(record(0).encode());
+		data.extend(record(1).encode());
+		// An event of a pallet index the runtime does not have.
+		data.extend(Phase::ApplyExtrinsic(2).encode());
+		data.extend([u8::MAX, 0, 0, 0]);
+
+		let (records, error) = decode_events(&data);
+		assert_eq!(records, vec![record(0), record(1)]);
+		assert_eq!(error.map(|(index, _)| index), Some(2));
+	}
+
+	#[test]
+	fn no_events() {
+		assert_eq!(decode_events(&[]).0, vec![]);
+		assert_eq!(decode_events(&Vec::<EventRecord<Event, Hash>>::new().encode()), (vec![], None));
+	}
+}
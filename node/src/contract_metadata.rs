@@ -0,0 +1,998 @@
+//! A store of ink! contract metadata by code hash, the decoding of contract events with it, and
+//! the encoding of constructor and message calls for the `contract` commands.
+//!
+//! Metadata is registered as the JSON file `cargo contract build` writes. Both the `V1` layout
+//! of ink! 3.0 release candidates, which our contracts use, and the `V3` layout of ink! 3.0 are
+//! understood. Registered metadata is kept in one `<code hash>.json` file per contract, so it
+//! survives restarts.
+
+use codec::{Compact, Decode, Encode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sp_core::{
+	bytes::to_hex,
+	crypto::{AccountId32, Ss58Codec},
+	H256,
+};
+use std::{
+	collections::HashMap,
+	path::PathBuf,
+	str::FromStr,
+	sync::{Arc, RwLock},
+};
+
+/// How deeply types may nest, as a guard against recursive type definitions.
+const MAX_DEPTH: u32 = 64;
+
+/// Registered contract metadata, by code hash.
+pub struct MetadataStore {
+	dir: Option<PathBuf>,
+	abis: RwLock<HashMap<H256, Arc<ContractAbi>>>,
+}
+
+impl MetadataStore {
+	/// Opens the store kept in `dir`, or an in-memory store without one.
+	pub fn open(dir: Option<PathBuf>) -> Result<Self, String> {
+		let mut abis = HashMap::new();
+		if let Some(dir) = &dir {
+			std::fs::create_dir_all(dir)
+				.map_err(|e| format!("Error creating `{}`: {}", dir.display(), e))?;
+			let entries = std::fs::read_dir(dir)
+				.map_err(|e| format!("Error reading `{}`: {}", dir.display(), e))?;
+			for entry in entries {
+				let path = entry.map_err(|e| e.to_string())?.path();
+				let code_hash = match path.file_stem().and_then(|s| s.to_str()) {
+					Some(stem) if path.extension().map_or(false, |e| e == "json") =>
+						match stem.parse::<H256>() {
+							Ok(code_hash) => code_hash,
+							Err(_) => continue,
+						},
+					_ => continue,
+				};
+				let abi = std::fs::read_to_string(&path)
+					.map_err(|e| e.to_string())
+					.and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+					.and_then(|metadata| ContractAbi::from_metadata(&metadata))
+					.map_err(|e| format!("Error loading `{}`: {}", path.display(), e))?;
+				abis.insert(code_hash, Arc::new(abi));
+			}
+		}
+		Ok(Self { dir, abis: RwLock::new(abis) })
+	}
+
+	/// Registers `metadata` for the contract code with `code_hash`, replacing any metadata
+	/// registered before.
+	///
+	/// Fails if the metadata cannot be used to decode events, or if it names another code hash.
+	pub fn register(&self, code_hash: H256, metadata: &Value) -> Result<(), String> {
+		if let Some(hash) = metadata.pointer("/source/hash").and_then(Value::as_str) {
+			let hash =
+				hash.parse::<H256>().map_err(|_| format!("invalid source hash `{}`", hash))?;
+			if hash != code_hash {
+				return Err(format!("metadata is for code hash {:?}, not {:?}", hash, code_hash))
+			}
+		}
+		let abi = ContractAbi::from_metadata(metadata)?;
+
+		if let Some(dir) = &self.dir {
+			let path = dir.join(format!("{:?}.json", code_hash));
+			std::fs::write(&path, metadata.to_string())
+				.map_err(|e| format!("Error writing `{}`: {}", path.display(), e))?;
+		}
+		self.abis
+			.write()
+			.expect("lock is not poisoned; qed")
+			.insert(code_hash, Arc::new(abi));
+		Ok(())
+	}
+
+	/// The metadata registered for `code_hash`.
+	pub fn get(&self, code_hash: &H256) -> Option<Arc<ContractAbi>> {
+		self.abis.read().expect("lock is not poisoned; qed").get(code_hash).cloned()
+	}
+
+	/// Decodes the data of an event emitted by a contract with `code_hash`.
+	pub fn decode_event(&self, code_hash: &H256, data: &[u8]) -> Result<DecodedEvent, String> {
+		self.get(code_hash)
+			.ok_or_else(|| "no metadata is registered for the code hash".to_string())?
+			.decode_event(data)
+	}
+}
+
+/// A contract event decoded with its metadata.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DecodedEvent {
+	/// Name of the event, such as `Transfer`.
+	pub name: String,
+	/// Fields of the event, in order.
+	pub fields: Vec<DecodedField>,
+}
+
+/// A field of a [`DecodedEvent`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DecodedField {
+	pub name: String,
+	/// Whether the field is `#[ink(topic)]`, and so also published as a topic.
+	pub indexed: bool,
+	pub value: Value,
+}
+
+/// The parts of a contract's metadata needed to encode its calls and decode its events.
+pub struct ContractAbi {
+	constructors: Vec<CallSpec>,
+	messages: Vec<CallSpec>,
+	events: Vec<EventSpec>,
+	types: HashMap<u32, Type>,
+}
+
+/// A constructor or message of a contract.
+#[derive(Deserialize)]
+pub struct CallSpec {
+	#[serde(alias = "name")]
+	label: Label,
+	selector: String,
+	#[serde(default)]
+	args: Vec<CallArg>,
+	#[serde(default, rename = "returnType")]
+	return_type: Option<TypeRef>,
+	#[serde(default)]
+	mutates: bool,
+}
+
+impl CallSpec {
+	/// The name, with the trait for trait messages, such as `Erc20::transfer`.
+	pub fn name(&self) -> String {
+		match &self.label {
+			Label::Name(name) => name.clone(),
+			Label::Path(path) => path.join("::"),
+		}
+	}
+
+	/// Whether the message may change the contract's storage.
+	pub fn mutates(&self) -> bool {
+		self.mutates
+	}
+
+	fn is_called(&self, name: &str) -> bool {
+		match &self.label {
+			Label::Name(label) => label == name,
+			Label::Path(path) =>
+				path.last().map_or(false, |last| last == name) || self.name() == name,
+		}
+	}
+}
+
+/// A name, or in the `V1` layout the path of a constructor or message.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Label {
+	Name(String),
+	Path(Vec<String>),
+}
+
+#[derive(Deserialize)]
+struct CallArg {
+	#[serde(alias = "name")]
+	label: String,
+	#[serde(rename = "type")]
+	ty: TypeRef,
+}
+
+#[derive(Deserialize)]
+struct EventSpec {
+	#[serde(alias = "name")]
+	label: String,
+	#[serde(default)]
+	args: Vec<EventArg>,
+}
+
+#[derive(Deserialize)]
+struct EventArg {
+	#[serde(alias = "name")]
+	label: String,
+	#[serde(default)]
+	indexed: bool,
+	#[serde(rename = "type")]
+	ty: TypeRef,
+}
+
+#[derive(Deserialize)]
+struct TypeRef {
+	#[serde(rename = "type")]
+	id: u32,
+}
+
+/// An entry of the type registry: with its ID since `scale-info` 2, by position before.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RegistryEntry {
+	WithId {
+		id: u32,
+		#[serde(rename = "type")]
+		ty: Type,
+	},
+	Positional(Type),
+}
+
+#[derive(Deserialize)]
+struct Type {
+	#[serde(default)]
+	path: Vec<String>,
+	def: TypeDef,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum TypeDef {
+	Composite {
+		#[serde(default)]
+		fields: Vec<Field>,
+	},
+	Variant {
+		#[serde(default)]
+		variants: Vec<Variant>,
+	},
+	Sequence {
+		#[serde(rename = "type")]
+		ty: u32,
+	},
+	Array {
+		len: u32,
+		#[serde(rename = "type")]
+		ty: u32,
+	},
+	Tuple(Vec<u32>),
+	Primitive(Primitive),
+	Compact {
+		#[serde(rename = "type")]
+		ty: u32,
+	},
+	#[serde(alias = "bitsequence")]
+	BitSequence(Value),
+}
+
+#[derive(Deserialize)]
+struct Field {
+	name: Option<String>,
+	#[serde(rename = "type")]
+	ty: u32,
+}
+
+#[derive(Deserialize)]
+struct Variant {
+	name: String,
+	#[serde(default)]
+	fields: Vec<Field>,
+	index: Option<u8>,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Primitive {
+	Bool,
+	Char,
+	Str,
+	U8,
+	U16,
+	U32,
+	U64,
+	U128,
+	U256,
+	I8,
+	I16,
+	I32,
+	I64,
+	I128,
+	I256,
+}
+
+impl ContractAbi {
+	/// Reads the events and types of ink! metadata.
+	pub fn from_metadata(metadata: &Value) -> Result<Self, String> {
+		let versioned = ["V3", "V2", "V1"]
+			.iter()
+			.find_map(|version| metadata.get(version))
+			.unwrap_or(metadata);
+		let events = versioned
+			.pointer("/spec/events")
+			.ok_or("metadata has no `spec.events`, is it ink! metadata?")?;
+		let events: Vec<EventSpec> = serde_json::from_value(events.clone())
+			.map_err(|e| format!("invalid `spec.events`: {}", e))?;
+		let calls = |field: &str| match versioned.pointer(&format!("/spec/{}", field)) {
+			Some(calls) => serde_json::from_value::<Vec<CallSpec>>(calls.clone())
+				.map_err(|e| format!("invalid `spec.{}`: {}", field, e)),
+			None => Ok(Vec::new()),
+		};
+		let constructors = calls("constructors")?;
+		let messages = calls("messages")?;
+		let types = versioned.get("types").ok_or("metadata has no `types`")?;
+		let entries: Vec<RegistryEntry> =
+			serde_json::from_value(types.clone()).map_err(|e| format!("invalid `types`: {}", e))?;
+		let types = entries
+			.into_iter()
+			.enumerate()
+			.map(|(position, entry)| match entry {
+				RegistryEntry::WithId { id, ty } => (id, ty),
+				RegistryEntry::Positional(ty) => (position as u32, ty),
+			})
+			.collect::<HashMap<_, _>>();
+
+		let abi = Self { constructors, messages, events, types };
+		let calls = abi.constructors.iter().chain(&abi.messages);
+		let call_types = calls
+			.flat_map(|call| call.args.iter().map(|arg| &arg.ty).chain(call.return_type.as_ref()));
+		for ty in abi
+			.events
+			.iter()
+			.flat_map(|event| &event.args)
+			.map(|arg| &arg.ty)
+			.chain(call_types)
+		{
+			abi.resolve(ty.id)?;
+		}
+		Ok(abi)
+	}
+
+	/// The constructor called `name`.
+	pub fn constructor(&self, name: &str) -> Result<&CallSpec, String> {
+		self.constructors
+			.iter()
+			.find(|constructor| constructor.is_called(name))
+			.ok_or_else(|| format!("no constructor `{}`", name))
+	}
+
+	/// The message called `name`, with or without its trait.
+	pub fn message(&self, name: &str) -> Result<&CallSpec, String> {
+		self.messages
+			.iter()
+			.find(|message| message.is_called(name))
+			.ok_or_else(|| format!("no message `{}`", name))
+	}
+
+	/// Encodes a call of a constructor or message: its selector followed by `args`.
+	///
+	/// Arguments are JSON values like those decoding returns. Byte strings may also be given
+	/// as text and integers as decimal strings.
+	pub fn encode_call(&self, call: &CallSpec, args: &[Value]) -> Result<Vec<u8>, String> {
+		if args.len() != call.args.len() {
+			let names = call.args.iter().map(|arg| arg.label.as_str()).collect::<Vec<_>>();
+			return Err(format!(
+				"`{}` takes {} arguments ({}), got {}",
+				call.name(),
+				names.len(),
+				names.join(", "),
+				args.len()
+			))
+		}
+		let mut out = sp_core::bytes::from_hex(&call.selector)
+			.map_err(|_| format!("invalid selector `{}`", call.selector))?;
+		for (arg, value) in call.args.iter().zip(args) {
+			self.encode(arg.ty.id, value, &mut out, 0)
+				.map_err(|e| format!("argument `{}`: {}", arg.label, e))?;
+		}
+		Ok(out)
+	}
+
+	/// Decodes what a message returned.
+	pub fn decode_return(&self, call: &CallSpec, data: &[u8]) -> Result<Value, String> {
+		let mut input = data;
+		let value = match &call.return_type {
+			Some(ty) => self.decode(ty.id, &mut input, 0)?,
+			None => Value::Null,
+		};
+		if !input.is_empty() {
+			return Err(format!("{} bytes left after decoding `{}`", input.len(), call.name()))
+		}
+		Ok(value)
+	}
+
+	/// Decodes the data of a `ContractEmitted` event: the index of the event in the metadata,
+	/// followed by its fields.
+	pub fn decode_event(&self, data: &[u8]) -> Result<DecodedEvent, String> {
+		let (index, mut input) = data.split_first().ok_or("empty event data")?;
+		let event = self
+			.events
+			.get(*index as usize)
+			.ok_or_else(|| format!("no event with index {}", index))?;
+		let fields = event
+			.args
+			.iter()
+			.map(|arg| {
+				let value = self
+					.decode(arg.ty.id, &mut input, 0)
+					.map_err(|e| format!("field `{}` of `{}`: {}", arg.label, event.label, e))?;
+				Ok(DecodedField { name: arg.label.clone(), indexed: arg.indexed, value })
+			})
+			.collect::<Result<_, String>>()?;
+		if !input.is_empty() {
+			return Err(format!("{} bytes left after decoding `{}`", input.len(), event.label))
+		}
+		Ok(DecodedEvent { name: event.label.clone(), fields })
+	}
+
+	fn resolve(&self, id: u32) -> Result<&Type, String> {
+		self.types.get(&id).ok_or_else(|| format!("unknown type {}", id))
+	}
+
+	fn is_u8(&self, id: u32) -> bool {
+		matches!(self.types.get(&id).map(|ty| &ty.def), Some(TypeDef::Primitive(Primitive::U8)))
+	}
+
+	/// Whether `fields` are a single `[u8; 32]`, like those of `AccountId`.
+	fn is_bytes32(&self, fields: &[Field]) -> bool {
+		match fields {
+			[field] => matches!(
+				self.types.get(&field.ty).map(|ty| &ty.def),
+				Some(TypeDef::Array { len: 32, ty }) if self.is_u8(*ty)
+			),
+			_ => false,
+		}
+	}
+
+	/// Decodes a value of type `id` into JSON.
+	///
+	/// Byte strings become hex, 32-byte `AccountId`s SS58 addresses, `Option`s their value or
+	/// `null`, and integers that JSON numbers cannot hold exactly decimal strings.
+	fn decode(&self, id: u32, input: &mut &[u8], depth: u32) -> Result<Value, String> {
+		if depth > MAX_DEPTH {
+			return Err("types nest too deeply".into())
+		}
+		let ty = self.resolve(id)?;
+		let depth = depth + 1;
+		match &ty.def {
+			TypeDef::Composite { fields } => {
+				if ty.path.last().map_or(false, |name| name == "AccountId") &&
+					self.is_bytes32(fields)
+				{
+					let raw = <[u8; 32]>::decode(input).map_err(|e| e.to_string())?;
+					return Ok(Value::String(AccountId32::new(raw).to_ss58check()))
+				}
+				self.decode_fields(fields, input, depth)
+			},
+			TypeDef::Variant { variants } => {
+				let index = u8::decode(input).map_err(|e| e.to_string())?;
+				let variant = variants
+					.iter()
+					.enumerate()
+					.find(|(position, v)| v.index.unwrap_or(*position as u8) == index)
+					.map(|(_, variant)| variant)
+					.ok_or_else(|| format!("no variant with index {}", index))?;
+				if ty.path == ["Option"] {
+					return self.decode_fields(&variant.fields, input, depth)
+				}
+				if variant.fields.is_empty() {
+					return Ok(Value::String(variant.name.clone()))
+				}
+				let fields = self.decode_fields(&variant.fields, input, depth)?;
+				Ok(Value::Object(Map::from_iter([(variant.name.clone(), fields)])))
+			},
+			TypeDef::Sequence { ty } => {
+				let len = Compact::<u32>::decode(input).map_err(|e| e.to_string())?.0;
+				self.decode_items(*ty, len, input, depth)
+			},
+			TypeDef::Array { len, ty } => self.decode_items(*ty, *len, input, depth),
+			TypeDef::Tuple(types) => match &types[..] {
+				[] => Ok(Value::Null),
+				types => types.iter().map(|ty| self.decode(*ty, input, depth)).collect(),
+			},
+			TypeDef::Primitive(primitive) => decode_primitive(*primitive, input),
+			TypeDef::Compact { .. } => {
+				let value = Compact::<u128>::decode(input).map_err(|e| e.to_string())?.0;
+				Ok(u128_value(value))
+			},
+			TypeDef::BitSequence(_) => Err("bit sequences are not supported".into()),
+		}
+	}
+
+	fn decode_fields(
+		&self,
+		fields: &[Field],
+		input: &mut &[u8],
+		depth: u32,
+	) -> Result<Value, String> {
+		match fields {
+			[] => Ok(Value::Null),
+			[field] if field.name.is_none() => self.decode(field.ty, input, depth),
+			fields if fields.iter().all(|field| field.name.is_some()) => fields
+				.iter()
+				.map(|field| {
+					let name = field.name.clone().unwrap_or_default();
+					Ok((name, self.decode(field.ty, input, depth)?))
+				})
+				.collect::<Result<Map<_, _>, String>>()
+				.map(Value::Object),
+			fields => fields.iter().map(|field| self.decode(field.ty, input, depth)).collect(),
+		}
+	}
+
+	fn decode_items(
+		&self,
+		ty: u32,
+		len: u32,
+		input: &mut &[u8],
+		depth: u32,
+	) -> Result<Value, String> {
+		if self.is_u8(ty) {
+			let len = len as usize;
+			if input.len() < len {
+				return Err("not enough data".into())
+			}
+			let (bytes, rest) = input.split_at(len);
+			*input = rest;
+			return Ok(Value::String(to_hex(bytes, false)))
+		}
+		(0..len).map(|_| self.decode(ty, input, depth)).collect()
+	}
+
+	/// Encodes a JSON value as type `id`, the inverse of `decode`.
+	fn encode(&self, id: u32, value: &Value, out: &mut Vec<u8>, depth: u32) -> Result<(), String> {
+		if depth > MAX_DEPTH {
+			return Err("types nest too deeply".into())
+		}
+		let ty = self.resolve(id)?;
+		let depth = depth + 1;
+		match &ty.def {
+			TypeDef::Composite { fields } => {
+				if ty.path.last().map_or(false, |name| name == "AccountId") &&
+					self.is_bytes32(fields)
+				{
+					let account = value
+						.as_str()
+						.and_then(|address| AccountId32::from_ss58check(address).ok())
+						.ok_or_else(|| format!("expected an SS58 address, got {}", value))?;
+					out.extend_from_slice(account.as_ref());
+					return Ok(())
+				}
+				self.encode_fields(fields, value, out, depth)
+			},
+			TypeDef::Variant { variants } => {
+				let is_option = ty.path == ["Option"];
+				let (name, fields) = match value {
+					Value::Null if is_option => ("None", &Value::Null),
+					_ if is_option => ("Some", value),
+					Value::String(name) => (name.as_str(), &Value::Null),
+					Value::Object(object) if object.len() == 1 => object
+						.iter()
+						.next()
+						.map(|(name, fields)| (name.as_str(), fields))
+						.expect("object has one entry; qed"),
+					_ =>
+						return Err(format!(
+							"expected a variant name or {{\"<variant>\": <fields>}}, got {}",
+							value
+						)),
+				};
+				let (position, variant) = variants
+					.iter()
+					.enumerate()
+					.find(|(_, variant)| variant.name == name)
+					.ok_or_else(|| format!("no variant `{}`", name))?;
+				out.push(variant.index.unwrap_or(position as u8));
+				self.encode_fields(&variant.fields, fields, out, depth)
+			},
+			TypeDef::Sequence { ty } => {
+				if let (true, Some(text)) = (self.is_u8(*ty), value.as_str()) {
+					let bytes = string_bytes(text)?;
+					Compact(bytes.len() as u32).encode_to(out);
+					out.extend(bytes);
+					return Ok(())
+				}
+				let items =
+					value.as_array().ok_or_else(|| format!("expected an array, got {}", value))?;
+				Compact(items.len() as u32).encode_to(out);
+				items.iter().try_for_each(|item| self.encode(*ty, item, out, depth))
+			},
+			TypeDef::Array { len, ty } => {
+				if let (true, Some(text)) = (self.is_u8(*ty), value.as_str()) {
+					let bytes = string_bytes(text)?;
+					if bytes.len() != *len as usize {
+						return Err(format!("expected {} bytes, got {}", len, bytes.len()))
+					}
+					out.extend(bytes);
+					return Ok(())
+				}
+				let items = value
+					.as_array()
+					.filter(|items| items.len() == *len as usize)
+					.ok_or_else(|| format!("expected an array of {} values, got {}", len, value))?;
+				items.iter().try_for_each(|item| self.encode(*ty, item, out, depth))
+			},
+			TypeDef::Tuple(types) => match &types[..] {
+				[] => Ok(()),
+				types => {
+					let items = value
+						.as_array()
+						.filter(|items| items.len() == types.len())
+						.ok_or_else(|| {
+							format!("expected an array of {} values, got {}", types.len(), value)
+						})?;
+					types
+						.iter()
+						.zip(items)
+						.try_for_each(|(ty, item)| self.encode(*ty, item, out, depth))
+				},
+			},
+			TypeDef::Primitive(primitive) => encode_primitive(*primitive, value, out),
+			TypeDef::Compact { .. } => {
+				Compact(parse_int::<u128>(value)?).encode_to(out);
+				Ok(())
+			},
+			TypeDef::BitSequence(_) => Err("bit sequences are not supported".into()),
+		}
+	}
+
+	fn encode_fields(
+		&self,
+		fields: &[Field],
+		value: &Value,
+		out: &mut Vec<u8>,
+		depth: u32,
+	) -> Result<(), String> {
+		match fields {
+			[] => Ok(()),
+			[field] if field.name.is_none() => self.encode(field.ty, value, out, depth),
+			fields if fields.iter().all(|field| field.name.is_some()) => {
+				let object = value
+					.as_object()
+					.ok_or_else(|| format!("expected an object, got {}", value))?;
+				fields.iter().try_for_each(|field| {
+					let name = field.name.as_deref().unwrap_or_default();
+					let value =
+						object.get(name).ok_or_else(|| format!("missing field `{}`", name))?;
+					self.encode(field.ty, value, out, depth)
+				})
+			},
+			fields => {
+				let items =
+					value.as_array().filter(|items| items.len() == fields.len()).ok_or_else(
+						|| format!("expected an array of {} values, got {}", fields.len(), value),
+					)?;
+				fields
+					.iter()
+					.zip(items)
+					.try_for_each(|(field, item)| self.encode(field.ty, item, out, depth))
+			},
+		}
+	}
+}
+
+/// The bytes of a `0x`-prefixed hex string, or else of the text itself.
+fn string_bytes(text: &str) -> Result<Vec<u8>, String> {
+	match text.strip_prefix("0x") {
+		Some(_) =>
+			sp_core::bytes::from_hex(text).map_err(|e| format!("invalid hex `{}`: {}", text, e)),
+		None => Ok(text.as_bytes().to_vec()),
+	}
+}
+
+/// An integer given as a JSON number or a decimal string.
+fn parse_int<T: TryFrom<i128> + FromStr>(value: &Value) -> Result<T, String> {
+	let int = match value {
+		Value::Number(number) => number
+			.as_i64()
+			.map(i128::from)
+			.or_else(|| number.as_u64().map(i128::from))
+			.and_then(|int| T::try_from(int).ok()),
+		Value::String(text) => text.parse().ok(),
+		_ => None,
+	};
+	int.ok_or_else(|| format!("expected an integer in range, got {}", value))
+}
+
+fn encode_primitive(primitive: Primitive, value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+	let text = || value.as_str().ok_or_else(|| format!("expected a string, got {}", value));
+	match primitive {
+		Primitive::Bool => value
+			.as_bool()
+			.ok_or_else(|| format!("expected a bool, got {}", value))?
+			.encode_to(out),
+		Primitive::Char => {
+			let mut chars = text()?.chars();
+			match (chars.next(), chars.next()) {
+				(Some(c), None) => (c as u32).encode_to(out),
+				_ => return Err(format!("expected a single character, got {}", value)),
+			}
+		},
+		Primitive::Str => text()?.encode_to(out),
+		Primitive::U8 => parse_int::<u8>(value)?.encode_to(out),
+		Primitive::U16 => parse_int::<u16>(value)?.encode_to(out),
+		Primitive::U32 => parse_int::<u32>(value)?.encode_to(out),
+		Primitive::U64 => parse_int::<u64>(value)?.encode_to(out),
+		Primitive::U128 => parse_int::<u128>(value)?.encode_to(out),
+		Primitive::I8 => parse_int::<i8>(value)?.encode_to(out),
+		Primitive::I16 => parse_int::<i16>(value)?.encode_to(out),
+		Primitive::I32 => parse_int::<i32>(value)?.encode_to(out),
+		Primitive::I64 => parse_int::<i64>(value)?.encode_to(out),
+		Primitive::I128 => parse_int::<i128>(value)?.encode_to(out),
+		Primitive::U256 | Primitive::I256 => {
+			let bytes = sp_core::bytes::from_hex(text()?).map_err(|e| e.to_string())?;
+			if bytes.len() != 32 {
+				return Err(format!("expected 32 bytes of hex, got {}", value))
+			}
+			out.extend(bytes);
+		},
+	}
+	Ok(())
+}
+
+fn decode_primitive(primitive: Primitive, input: &mut &[u8]) -> Result<Value, String> {
+	fn decode<T: Decode>(input: &mut &[u8]) -> Result<T, String> {
+		T::decode(input).map_err(|e| e.to_string())
+	}
+	Ok(match primitive {
+		Primitive::Bool => json!(decode::<bool>(input)?),
+		Primitive::Char => {
+			let c = char::from_u32(decode::<u32>(input)?).ok_or("invalid char")?;
+			Value::String(c.to_string())
+		},
+		Primitive::Str => json!(decode::<String>(input)?),
+		Primitive::U8 => json!(decode::<u8>(input)?),
+		Primitive::U16 => json!(decode::<u16>(input)?),
+		Primitive::U32 => json!(decode::<u32>(input)?),
+		Primitive::U64 => json!(decode::<u64>(input)?),
+		Primitive::U128 => u128_value(decode::<u128>(input)?),
+		Primitive::I8 => json!(decode::<i8>(input)?),
+		Primitive::I16 => json!(decode::<i16>(input)?),
+		Primitive::I32 => json!(decode::<i32>(input)?),
+		Primitive::I64 => json!(decode::<i64>(input)?),
+		Primitive::I128 => Value::String(decode::<i128>(input)?.to_string()),
+		Primitive::U256 | Primitive::I256 =>
+			Value::String(to_hex(&decode::<[u8; 32]>(input)?, false)),
+	})
+}
+
+/// A JSON number if it fits into a `u64`, a decimal string otherwise.
+fn u128_value(value: u128) -> Value {
+	match u64::try_from(value) {
+		Ok(value) => json!(value),
+		Err(_) => Value::String(value.to_string()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Metadata of an event `Transfer { #[ink(topic)] from: Option<AccountId>, to: AccountId,
+	/// value: Balance }`, a constructor `new(Balance)` and messages `transfer(AccountId, Balance)`
+	/// and `balance_of(AccountId) -> Balance`, in the `V1` layout of ink! 3.0.0-rc6.
+	fn metadata() -> Value {
+		json!({
+			"metadataVersion": "0.1.0",
+			"source": {
+				"hash": format!("{:?}", H256::repeat_byte(1)),
+				"language": "ink! 3.0.0-rc6",
+				"compiler": "rustc 1.58.0-nightly"
+			},
+			"contract": { "name": "solar_ft", "version": "0.1.0", "authors": [] },
+			"V1": {
+				"spec": {
+					"constructors": [
+						{
+							"args": [
+								{
+									"name": "initial_supply",
+									"type": { "displayName": ["Balance"], "type": 4 }
+								}
+							],
+							"docs": [],
+							"name": ["new"],
+							"selector": "0x9bae9d5e"
+						}
+					],
+					"docs": [],
+					"events": [
+						{ "args": [], "docs": [], "name": "Paused" },
+						{
+							"args": [
+								{
+									"docs": [],
+									"indexed": true,
+									"name": "from",
+									"type": { "displayName": ["Option"], "type": 3 }
+								},
+								{
+									"docs": [],
+									"indexed": false,
+									"name": "to",
+									"type": { "displayName": ["AccountId"], "type": 0 }
+								},
+								{
+									"docs": [],
+									"indexed": false,
+									"name": "value",
+									"type": { "displayName": ["Balance"], "type": 4 }
+								}
+							],
+							"docs": [],
+							"name": "Transfer"
+						}
+					],
+					"messages": [
+						{
+							"args": [
+								{
+									"name": "to",
+									"type": { "displayName": ["AccountId"], "type": 0 }
+								},
+								{
+									"name": "value",
+									"type": { "displayName": ["Balance"], "type": 4 }
+								}
+							],
+							"docs": [],
+							"mutates": true,
+							"name": ["transfer"],
+							"payable": false,
+							"returnType": null,
+							"selector": "0x84a15da1"
+						},
+						{
+							"args": [
+								{
+									"name": "owner",
+									"type": { "displayName": ["AccountId"], "type": 0 }
+								}
+							],
+							"docs": [],
+							"mutates": false,
+							"name": ["Erc20", "balance_of"],
+							"payable": false,
+							"returnType": { "displayName": ["Balance"], "type": 4 },
+							"selector": "0x0f755a56"
+						}
+					]
+				},
+				"storage": { "struct": { "fields": [] } },
+				"types": [
+					{
+						"def": {
+							"composite": { "fields": [{ "type": 1, "typeName": "[u8; 32]" }] }
+						},
+						"path": ["ink_env", "types", "AccountId"]
+					},
+					{ "def": { "array": { "len": 32, "type": 2 } } },
+					{ "def": { "primitive": "u8" } },
+					{
+						"def": {
+							"variant": {
+								"variants": [
+									{ "index": 0, "name": "None" },
+									{ "fields": [{ "type": 0 }], "index": 1, "name": "Some" }
+								]
+							}
+						},
+						"params": [0],
+						"path": ["Option"]
+					},
+					{ "def": { "primitive": "u128" } }
+				]
+			}
+		})
+	}
+
+	#[test]
+	fn decodes_events() {
+		let abi = ContractAbi::from_metadata(&metadata()).unwrap();
+		let alice = AccountId32::new([1; 32]);
+		let bob = AccountId32::new([2; 32]);
+
+		let data = (1u8, Some(alice.clone()), bob.clone(), u128::MAX).encode();
+		assert_eq!(
+			abi.decode_event(&data).unwrap(),
+			DecodedEvent {
+				name: "Transfer".into(),
+				fields: vec![
+					DecodedField {
+						name: "from".into(),
+						indexed: true,
+						value: json!(alice.to_ss58check()),
+					},
+					DecodedField {
+						name: "to".into(),
+						indexed: false,
+						value: json!(bob.to_ss58check())
+					},
+					DecodedField {
+						name: "value".into(),
+						indexed: false,
+						value: json!(u128::MAX.to_string()),
+					},
+				],
+			}
+		);
+
+		let data = (1u8, None::<AccountId32>, bob, 10u128).encode();
+		let event = abi.decode_event(&data).unwrap();
+		assert_eq!(event.fields[0].value, Value::Null);
+		assert_eq!(event.fields[2].value, json!(10));
+
+		assert_eq!(
+			abi.decode_event(&[0]).unwrap(),
+			DecodedEvent { name: "Paused".into(), fields: vec![] }
+		);
+		assert!(abi.decode_event(&[2]).is_err());
+		assert!(abi.decode_event(&[0, 0]).is_err());
+	}
+
+	#[test]
+	fn encodes_calls() {
+		let abi = ContractAbi::from_metadata(&metadata()).unwrap();
+		let bob = AccountId32::new([2; 32]);
+
+		let new = abi.constructor("new").unwrap();
+		assert_eq!(
+			abi.encode_call(new, &[json!("1000")]).unwrap(),
+			([0x9b, 0xae, 0x9d, 0x5e], 1000u128).encode()
+		);
+		assert!(abi.constructor("default").is_err());
+
+		let transfer = abi.message("transfer").unwrap();
+		assert!(transfer.mutates());
+		assert_eq!(
+			abi.encode_call(transfer, &[json!(bob.to_ss58check()), json!(10)]).unwrap(),
+			([0x84, 0xa1, 0x5d, 0xa1], bob.clone(), 10u128).encode()
+		);
+		assert!(abi.encode_call(transfer, &[json!(bob.to_ss58check())]).is_err());
+		assert!(abi.encode_call(transfer, &[json!("bob"), json!(10)]).is_err());
+		assert!(abi.encode_call(transfer, &[json!(bob.to_ss58check()), json!(-1)]).is_err());
+
+		let balance_of = abi.message("Erc20::balance_of").unwrap();
+		assert!(!balance_of.mutates());
+		assert_eq!(abi.message("balance_of").unwrap().name(), "Erc20::balance_of");
+		assert_eq!(
+			abi.decode_return(balance_of, &u128::MAX.encode()).unwrap(),
+			json!(u128::MAX.to_string())
+		);
+		assert!(abi.decode_return(balance_of, &[0]).is_err());
+		assert_eq!(abi.decode_return(transfer, &[]).unwrap(), Value::Null);
+	}
+
+	#[test]
+	fn encodes_what_it_decodes() {
+		let abi = ContractAbi::from_metadata(&metadata()).unwrap();
+		let alice = AccountId32::new([1; 32]);
+		for value in [json!(alice.to_ss58check()), Value::Null] {
+			let mut out = Vec::new();
+			abi.encode(3, &value, &mut out, 0).unwrap();
+			assert_eq!(abi.decode(3, &mut &out[..], 0).unwrap(), value);
+		}
+		let mut out = Vec::new();
+		abi.encode(1, &json!(format!("0x{}", "02".repeat(32))), &mut out, 0).unwrap();
+		assert_eq!(out, [2; 32]);
+		assert!(abi.encode(1, &json!("0x02"), &mut Vec::new(), 0).is_err());
+	}
+
+	#[test]
+	fn reads_registries_with_type_ids() {
+		let mut metadata = metadata();
+		let versioned = metadata["V1"].take();
+		let types = versioned["types"]
+			.as_array()
+			.unwrap()
+			.iter()
+			.enumerate()
+			.map(|(id, ty)| json!({ "id": id, "type": ty }))
+			.collect::<Vec<_>>();
+		let metadata = json!({ "V3": { "spec": versioned["spec"], "types": types } });
+
+		let abi = ContractAbi::from_metadata(&metadata).unwrap();
+		assert_eq!(abi.decode_event(&[0]).unwrap().name, "Paused");
+	}
+
+	#[test]
+	fn store_keeps_metadata() {
+		let dir = tempfile::tempdir().unwrap();
+		let code_hash = H256::repeat_byte(1);
+
+		let store = MetadataStore::open(Some(dir.path().into())).unwrap();
+		assert!(store.register(H256::repeat_byte(2), &metadata()).is_err());
+		assert!(store.register(code_hash, &json!({})).is_err());
+		store.register(code_hash, &metadata()).unwrap();
+		assert!(store.get(&code_hash).is_some());
+
+		let store = MetadataStore::open(Some(dir.path().into())).unwrap();
+		assert_eq!(store.get(&code_hash).unwrap().decode_event(&[0]).unwrap().name, "Paused");
+		assert!(store.get(&H256::repeat_byte(2)).is_none());
+	}
+}
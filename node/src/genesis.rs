@@ -0,0 +1,287 @@
+//! Genesis descriptions: TOML or JSON files listing the authorities, governance, endowments and
+//! well-known nodes of a chain together with its token properties, from which `build-genesis`
+//! and the `staging` preset build a chain spec.
+//!
+//! Addresses and keys are SS58 strings and peer IDs base58 strings, all parsed with errors naming
+//! the offending field. Balances are integers or, beyond the range of TOML integers, decimal
+//! strings. See `node/res/staging.toml` for an example.
+
+use sc_service::{config::MultiaddrWithPeerId, ChainType};
+use serde::Deserialize;
+use solar_node_runtime::{Balance, SS58Prefix, WASM_BINARY};
+use sp_core::Get;
+use std::path::Path;
+
+use crate::chain_spec::{
+	deserialize_balance, genesis_config, parse_ss58, properties, well_known_node, Allocation,
+	ChainSpec, GenesisParams,
+};
+
+/// A genesis description, as read from a file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisDescription {
+	/// Human-readable name of the chain.
+	pub name: String,
+	/// Chain ID, also the name of the chain's database directory.
+	pub id: String,
+	/// `Live`, `Local` or `Development`.
+	#[serde(default = "live")]
+	pub chain_type: ChainType,
+	/// Network protocol ID, keeping the chain's peers apart from other chains'.
+	#[serde(default)]
+	pub protocol_id: Option<String>,
+	/// Multiaddresses of the boot nodes, ending in `/p2p/<peer id>`.
+	#[serde(default)]
+	pub boot_nodes: Vec<String>,
+	/// Token properties shown by wallets.
+	pub properties: TokenProperties,
+	/// The sudo key, if any.
+	#[serde(default)]
+	pub sudo: Option<String>,
+	/// Council members.
+	#[serde(default)]
+	pub council: Vec<String>,
+	/// Technical committee members, the council if missing.
+	#[serde(default)]
+	pub technical_committee: Option<Vec<String>>,
+	/// The initial validators.
+	pub authorities: Vec<Authority>,
+	/// Accounts endowed with a balance.
+	#[serde(default)]
+	pub endowments: Vec<Endowment>,
+	/// Allocations that may vest.
+	#[serde(default)]
+	pub allocations: Vec<Allocation>,
+	/// Nodes allowed by `pallet_node_authorization`.
+	#[serde(default)]
+	pub well_known_nodes: Vec<WellKnownNode>,
+}
+
+fn live() -> ChainType {
+	ChainType::Live
+}
+
+/// Token properties of a [`GenesisDescription`].
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenProperties {
+	pub token_symbol: String,
+	pub token_decimals: u8,
+	pub ss58_format: u16,
+}
+
+/// A validator account with its Aura (sr25519) and Grandpa (ed25519) session keys.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Authority {
+	pub account: String,
+	pub aura: String,
+	pub grandpa: String,
+}
+
+/// An account endowed with a balance.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Endowment {
+	pub account: String,
+	#[serde(deserialize_with = "deserialize_balance")]
+	pub balance: Balance,
+}
+
+/// A node allowed by `pallet_node_authorization`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WellKnownNode {
+	pub peer_id: String,
+	pub owner: String,
+}
+
+impl GenesisDescription {
+	/// Reads a description from a `.toml` or `.json` file.
+	pub fn from_file(path: &Path) -> Result<Self, String> {
+		let content = std::fs::read_to_string(path)
+			.map_err(|e| format!("Error reading genesis file `{}`: {}", path.display(), e))?;
+		match path.extension().and_then(|e| e.to_str()) {
+			Some("toml") => Self::from_toml(&content),
+			Some("json") => serde_json::from_str(&content).map_err(|e| e.to_string()),
+			_ => Err("expected a `.toml` or `.json` file".into()),
+		}
+		.map_err(|e| format!("Error parsing genesis file `{}`: {}", path.display(), e))
+	}
+
+	/// Parses a description from TOML.
+	pub fn from_toml(content: &str) -> Result<Self, String> {
+		toml::from_str(content).map_err(|e| e.to_string())
+	}
+
+	/// Validates the description and builds the chain spec it describes.
+	pub fn into_chain_spec(self) -> Result<ChainSpec, String> {
+		let wasm_binary = WASM_BINARY.ok_or_else(|| "Wasm binary not available".to_string())?;
+
+		let boot_nodes = self
+			.boot_nodes
+			.iter()
+			.map(|addr| {
+				addr.parse::<MultiaddrWithPeerId>()
+					.map_err(|e| format!("boot node `{}`: {}", addr, e))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let TokenProperties { token_symbol, token_decimals, ss58_format } = &self.properties;
+		let properties = properties(token_symbol, *token_decimals, *ss58_format);
+		let params = self.params()?;
+
+		Ok(ChainSpec::from_genesis(
+			&self.name,
+			&self.id,
+			self.chain_type,
+			move || genesis_config(wasm_binary, params.clone()),
+			boot_nodes,
+			None,
+			self.protocol_id.as_deref(),
+			None,
+			Some(properties),
+			None,
+		))
+	}
+
+	/// Parses the accounts, keys and nodes of the description into validated genesis parameters.
+	///
+	/// Also checks the token properties, which are not part of the genesis parameters.
+	pub fn params(&self) -> Result<GenesisParams, String> {
+		if self.properties.ss58_format != SS58Prefix::get() {
+			return Err(format!(
+				"properties.ss58_format: {} differs from the runtime's SS58Prefix {}",
+				self.properties.ss58_format,
+				SS58Prefix::get()
+			))
+		}
+		let accounts = |field: &str, accounts: &[String]| {
+			accounts
+				.iter()
+				.enumerate()
+				.map(|(i, account)| parse_ss58(&format!("{}[{}]", field, i), account))
+				.collect::<Result<Vec<_>, _>>()
+		};
+		let council = accounts("council", &self.council)?;
+		let technical_committee = match &self.technical_committee {
+			Some(members) => accounts("technical_committee", members)?,
+			None => council.clone(),
+		};
+
+		let params = GenesisParams {
+			initial_authorities: self
+				.authorities
+				.iter()
+				.enumerate()
+				.map(|(i, a)| {
+					Ok((
+						parse_ss58(&format!("authorities[{}].account", i), &a.account)?,
+						parse_ss58(&format!("authorities[{}].aura", i), &a.aura)?,
+						parse_ss58(&format!("authorities[{}].grandpa", i), &a.grandpa)?,
+					))
+				})
+				.collect::<Result<_, String>>()?,
+			root_key: self.sudo.as_deref().map(|sudo| parse_ss58("sudo", sudo)).transpose()?,
+			council,
+			technical_committee,
+			endowments: self
+				.endowments
+				.iter()
+				.enumerate()
+				.map(|(i, e)| {
+					Ok((parse_ss58(&format!("endowments[{}].account", i), &e.account)?, e.balance))
+				})
+				.collect::<Result<_, String>>()?,
+			allocations: self.allocations.clone(),
+			well_known_nodes: self
+				.well_known_nodes
+				.iter()
+				.map(|n| well_known_node(&n.peer_id, &n.owner))
+				.collect::<Result<_, _>>()?,
+			stable_holders: vec![],
+		};
+		params.validate()?;
+		Ok(params)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solar_node_runtime::ExistentialDeposit;
+
+	fn staging() -> GenesisDescription {
+		GenesisDescription::from_toml(include_str!("../res/staging.toml")).unwrap()
+	}
+
+	fn error(description: GenesisDescription) -> String {
+		description.params().err().expect("the description is invalid")
+	}
+
+	#[test]
+	fn staging_is_valid() {
+		let params = staging().params().unwrap();
+		assert_eq!(params.initial_authorities.len(), 4);
+		assert_eq!(params.well_known_nodes.len(), 4);
+	}
+
+	#[test]
+	fn rejects_invalid_ss58() {
+		let mut description = staging();
+		description.council[1] = "5GHbL5YWjsaLL24PRAiX2YK8b3vcoqvStnMvRR75YrD5jTtz".into();
+		assert!(error(description).starts_with("council[1]: invalid SS58"));
+
+		let mut description = staging();
+		description.authorities[2].grandpa = "not an address".into();
+		assert!(error(description).starts_with("authorities[2].grandpa: invalid SS58"));
+	}
+
+	#[test]
+	fn rejects_other_ss58_formats() {
+		let mut description = staging();
+		description.properties.ss58_format = 60;
+		assert_eq!(
+			error(description),
+			"properties.ss58_format: 60 differs from the runtime's SS58Prefix 42"
+		);
+	}
+
+	#[test]
+	fn rejects_invalid_peer_ids() {
+		let mut description = staging();
+		description.well_known_nodes[0].peer_id = "12D3KooW0OIl".into();
+		assert!(error(description).contains("invalid base58 peer ID `12D3KooW0OIl`"));
+	}
+
+	#[test]
+	fn rejects_duplicates() {
+		let mut description = staging();
+		description.authorities[1].aura = description.authorities[0].aura.clone();
+		assert!(error(description).starts_with("Aura key"));
+
+		let mut description = staging();
+		description.authorities[3].grandpa = description.authorities[2].grandpa.clone();
+		assert!(error(description).starts_with("GRANDPA key"));
+
+		let mut description = staging();
+		description.authorities[1].account = description.authorities[0].account.clone();
+		assert!(error(description).starts_with("Authority"));
+
+		let mut description = staging();
+		description.well_known_nodes[1].peer_id = description.well_known_nodes[0].peer_id.clone();
+		assert!(error(description).starts_with("Well-known node"));
+
+		let mut description = staging();
+		description.endowments[1].account = description.endowments[0].account.clone();
+		assert!(error(description).starts_with("Account"));
+	}
+
+	#[test]
+	fn rejects_balances_below_the_existential_deposit() {
+		let mut description = staging();
+		description.endowments[2].balance = ExistentialDeposit::get() - 1;
+		assert!(error(description).contains("below the existential deposit"));
+	}
+}
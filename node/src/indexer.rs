@@ -0,0 +1,569 @@
+//! An indexer writing NFT, marketplace and contract events of finalized blocks into SQLite, for
+//! history that would otherwise take replaying blocks. Built with the `indexer` feature.
+//!
+//! The node indexes in the background with `--index-db <PATH>`, resuming from the first block
+//! missing in the database, and the `index` subcommand (re-)indexes a range of blocks. Events are
+//! read from the state of their block, so blocks whose state was pruned can only be indexed from
+//! an archive node (`--pruning archive`).
+//!
+//! Accounts are SS58 strings, hashes `0x`-prefixed hex, balances decimal strings (they do not
+//! fit into SQLite integers) and collection and token IDs integers. See [`SCHEMA`] for the
+//! tables.
+
+use std::{path::Path, sync::Arc};
+
+use futures::StreamExt;
+use rusqlite::{params, Connection, Transaction};
+use sc_client_api::BlockchainEvents;
+use solar_node_runtime::{AccountId, BlockNumber, Event, Hash};
+use sp_blockchain::HeaderBackend;
+use sp_core::crypto::Ss58Codec;
+use sp_runtime::{generic::BlockId, traits::Header};
+
+use crate::{
+	cli::IndexCmd,
+	contract_events::{block_events, code_hash, extrinsic_index},
+	contract_metadata::MetadataStore,
+	service::{block_timestamp, FullClient},
+};
+
+/// The tables of the index. Every table except `nft_owners` is keyed by the block number and
+/// the index of the event in the block, and `extrinsic_index` is `NULL` for events outside of
+/// extrinsics.
+pub const SCHEMA: &str = "
+-- Indexed blocks, with their timestamp in milliseconds.
+CREATE TABLE IF NOT EXISTS blocks (
+	number INTEGER PRIMARY KEY,
+	hash TEXT NOT NULL,
+	timestamp INTEGER
+);
+
+-- Mints (`from_account` is NULL), burns (`to_account` is NULL) and transfers of
+-- `pallet_solar_nft` tokens, including those into and out of marketplace escrow.
+CREATE TABLE IF NOT EXISTS nft_transfers (
+	block_number INTEGER NOT NULL,
+	event_index INTEGER NOT NULL,
+	extrinsic_index INTEGER,
+	collection_id INTEGER NOT NULL,
+	token_id INTEGER NOT NULL,
+	from_account TEXT,
+	to_account TEXT,
+	PRIMARY KEY (block_number, event_index)
+);
+CREATE INDEX IF NOT EXISTS nft_transfers_token ON nft_transfers (collection_id, token_id);
+CREATE INDEX IF NOT EXISTS nft_transfers_from ON nft_transfers (from_account);
+CREATE INDEX IF NOT EXISTS nft_transfers_to ON nft_transfers (to_account);
+
+-- The owner of every token as of the latest indexed mint, transfer or burn. `owner` is NULL
+-- for burned tokens, which are kept so that indexing an earlier block cannot bring them back:
+-- filter them out with `owner IS NOT NULL`.
+CREATE TABLE IF NOT EXISTS nft_owners (
+	collection_id INTEGER NOT NULL,
+	token_id INTEGER NOT NULL,
+	owner TEXT,
+	block_number INTEGER NOT NULL,
+	event_index INTEGER NOT NULL,
+	PRIMARY KEY (collection_id, token_id)
+);
+CREATE INDEX IF NOT EXISTS nft_owners_owner ON nft_owners (owner);
+
+-- Offers of `pallet_solar_market`: `kind` is `created`, `updated` or `cancelled`, and `price`
+-- is NULL for cancellations.
+CREATE TABLE IF NOT EXISTS market_offers (
+	block_number INTEGER NOT NULL,
+	event_index INTEGER NOT NULL,
+	extrinsic_index INTEGER,
+	kind TEXT NOT NULL,
+	seller TEXT NOT NULL,
+	collection_id INTEGER NOT NULL,
+	token_id INTEGER NOT NULL,
+	price TEXT,
+	PRIMARY KEY (block_number, event_index)
+);
+CREATE INDEX IF NOT EXISTS market_offers_token ON market_offers (collection_id, token_id);
+
+-- Sales of `pallet_solar_market`, with the commission rate in percent.
+CREATE TABLE IF NOT EXISTS market_trades (
+	block_number INTEGER NOT NULL,
+	event_index INTEGER NOT NULL,
+	extrinsic_index INTEGER,
+	collection_id INTEGER NOT NULL,
+	token_id INTEGER NOT NULL,
+	seller TEXT NOT NULL,
+	buyer TEXT NOT NULL,
+	price TEXT NOT NULL,
+	commission_rate INTEGER NOT NULL,
+	seller_income TEXT NOT NULL,
+	organiser_income TEXT NOT NULL,
+	PRIMARY KEY (block_number, event_index)
+);
+CREATE INDEX IF NOT EXISTS market_trades_token ON market_trades (collection_id, token_id);
+CREATE INDEX IF NOT EXISTS market_trades_seller ON market_trades (seller);
+CREATE INDEX IF NOT EXISTS market_trades_buyer ON market_trades (buyer);
+
+-- Events emitted by contracts, such as the `nft` and market contracts. `topics` is a JSON
+-- array. `name` and `fields`, a JSON array of `{name, indexed, value}`, are set when
+-- metadata is registered for the contract code through `solar_registerContractMetadata`.
+CREATE TABLE IF NOT EXISTS contract_events (
+	block_number INTEGER NOT NULL,
+	event_index INTEGER NOT NULL,
+	extrinsic_index INTEGER,
+	contract TEXT NOT NULL,
+	code_hash TEXT,
+	topics TEXT NOT NULL,
+	data BLOB NOT NULL,
+	name TEXT,
+	fields TEXT,
+	PRIMARY KEY (block_number, event_index)
+);
+CREATE INDEX IF NOT EXISTS contract_events_contract ON contract_events (contract, name);
+";
+
+/// Tables with rows per event, cleared when a block is indexed again.
+const EVENT_TABLES: [&str; 4] =
+	["nft_transfers", "market_offers", "market_trades", "contract_events"];
+
+/// An error while indexing.
+#[derive(Debug)]
+pub enum Error {
+	/// The block or its state is not available.
+	Chain(String),
+	/// Writing to the database failed.
+	Database(rusqlite::Error),
+}
+
+impl std::fmt::Display for Error {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Error::Chain(e) => write!(f, "{}", e),
+			Error::Database(e) => write!(f, "database error: {}", e),
+		}
+	}
+}
+
+impl From<rusqlite::Error> for Error {
+	fn from(e: rusqlite::Error) -> Self {
+		Error::Database(e)
+	}
+}
+
+/// The position of an event: block number, event index and extrinsic index.
+type EventPosition = (BlockNumber, u32, Option<u32>);
+
+/// Writes events into an SQLite database.
+pub struct Indexer {
+	conn: Connection,
+	contract_metadata: Arc<MetadataStore>,
+}
+
+impl Indexer {
+	/// Opens the database at `path`, creating it and its tables if missing.
+	pub fn open(path: &Path, contract_metadata: Arc<MetadataStore>) -> Result<Self, String> {
+		let conn = Connection::open(path)
+			.and_then(|conn| conn.execute_batch(SCHEMA).map(|()| conn))
+			.map_err(|e| format!("Error opening index `{}`: {}", path.display(), e))?;
+		Ok(Self { conn, contract_metadata })
+	}
+
+	/// The lowest block number that is not indexed yet.
+	///
+	/// Blocks that were skipped, or indexed out of order with `index --from`, leave gaps below
+	/// the highest indexed block, so this is not always the block after it.
+	pub fn first_missing(&self) -> Result<BlockNumber, Error> {
+		Ok(self.conn.query_row(
+			"SELECT CASE WHEN EXISTS (SELECT 1 FROM blocks WHERE number = 0) THEN \
+			 (SELECT MIN(number) + 1 FROM blocks AS b WHERE NOT EXISTS \
+			 (SELECT 1 FROM blocks WHERE number = b.number + 1)) ELSE 0 END",
+			[],
+			|row| row.get(0),
+		)?)
+	}
+
+	/// Indexes the canonical block `number`, replacing what was indexed for it before.
+	pub fn index_block(&mut self, client: &FullClient, number: BlockNumber) -> Result<(), Error> {
+		let chain_error = |e: String| Error::Chain(format!("block #{}: {}", number, e));
+		let hash = client
+			.hash(number)
+			.map_err(|e| chain_error(e.to_string()))?
+			.ok_or_else(|| chain_error("not found".into()))?;
+		let parent_hash = *client
+			.header(BlockId::Hash(hash))
+			.map_err(|e| chain_error(e.to_string()))?
+			.ok_or_else(|| chain_error("header not found".into()))?
+			.parent_hash();
+		let records = block_events(client, hash).map_err(chain_error)?;
+		let contracts = records
+			.iter()
+			.filter_map(|record| match &record.event {
+				Event::Contracts(pallet_contracts::Event::ContractEmitted { contract, .. }) =>
+					Some(contract),
+				_ => None,
+			})
+			.map(|contract| {
+				let code_hash =
+					code_hash(client, hash, parent_hash, contract).map_err(chain_error)?;
+				Ok((contract.clone(), code_hash))
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		let code_hash_of = |contract: &AccountId| {
+			contracts
+				.iter()
+				.find(|(c, _)| c == contract)
+				.and_then(|(_, code_hash)| *code_hash)
+		};
+		self.write_block(number, hash, block_timestamp(client, hash), &records, code_hash_of)
+	}
+
+	fn write_block(
+		&mut self,
+		number: BlockNumber,
+		hash: Hash,
+		timestamp: Option<u64>,
+		records: &[frame_system::EventRecord<Event, Hash>],
+		code_hash_of: impl Fn(&AccountId) -> Option<Hash>,
+	) -> Result<(), Error> {
+		let tx = self.conn.transaction()?;
+		for table in EVENT_TABLES {
+			tx.execute(&format!("DELETE FROM {} WHERE block_number = ?1", table), [number])?;
+		}
+		tx.execute(
+			"INSERT OR REPLACE INTO blocks (number, hash, timestamp) VALUES (?1, ?2, ?3)",
+			params![number, format!("{:?}", hash), timestamp.map(|t| t as i64)],
+		)?;
+
+		for (event_index, record) in records.iter().enumerate() {
+			let at = (number, event_index as u32, extrinsic_index(&record.phase));
+			match &record.event {
+				Event::Nft(event) => nft_event(&tx, at, event)?,
+				Event::Market(event) => market_event(&tx, at, event)?,
+				Event::Contracts(pallet_contracts::Event::ContractEmitted { contract, data }) => {
+					let code_hash = code_hash_of(contract);
+					let decoded = code_hash.and_then(|code_hash| {
+						self.contract_metadata.decode_event(&code_hash, data).ok()
+					});
+					let topics = record
+						.topics
+						.iter()
+						.map(|topic| format!("{:?}", topic))
+						.collect::<Vec<_>>();
+					tx.execute(
+						"INSERT INTO contract_events (block_number, event_index, extrinsic_index, \
+						 contract, code_hash, topics, data, name, fields) \
+						 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+						params![
+							at.0,
+							at.1,
+							at.2,
+							contract.to_ss58check(),
+							code_hash.map(|h| format!("{:?}", h)),
+							serde_json::to_string(&topics).expect("strings serialize; qed"),
+							data,
+							decoded.as_ref().map(|event| &event.name),
+							decoded.map(|event| serde_json::to_string(&event.fields)
+								.expect("decoded fields serialize; qed")),
+						],
+					)?;
+				},
+				_ => {},
+			}
+		}
+		Ok(tx.commit()?)
+	}
+}
+
+fn nft_event(
+	tx: &Transaction,
+	at: EventPosition,
+	event: &pallet_solar_nft::Event<solar_node_runtime::Runtime>,
+) -> rusqlite::Result<()> {
+	use pallet_solar_nft::Event::*;
+	let (collection_id, token_id, from, to) = match event {
+		Minted { owner, collection_id, token_id } => (collection_id, token_id, None, Some(owner)),
+		Burned { caller, collection_id, token_id } => (collection_id, token_id, Some(caller), None),
+		Transfer { from, to, collection_id, token_id } =>
+			(collection_id, token_id, Some(from), Some(to)),
+		_ => return Ok(()),
+	};
+	let (collection_id, token_id) = (*collection_id as i64, *token_id as i64);
+	tx.execute(
+		"INSERT INTO nft_transfers (block_number, event_index, extrinsic_index, collection_id, \
+		 token_id, from_account, to_account) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+		params![
+			at.0,
+			at.1,
+			at.2,
+			collection_id,
+			token_id,
+			from.map(|a| a.to_ss58check()),
+			to.map(|a| a.to_ss58check())
+		],
+	)?;
+
+	// Blocks may be indexed out of order, so only later events replace the owner. Burns leave
+	// a row with a NULL owner, which earlier mints and transfers cannot replace.
+	tx.execute(
+		"INSERT INTO nft_owners (collection_id, token_id, owner, block_number, event_index) \
+		 VALUES (?1, ?2, ?3, ?4, ?5) \
+		 ON CONFLICT (collection_id, token_id) DO UPDATE SET owner = excluded.owner, \
+		 block_number = excluded.block_number, event_index = excluded.event_index \
+		 WHERE (excluded.block_number, excluded.event_index) >= \
+		 (nft_owners.block_number, nft_owners.event_index)",
+		params![collection_id, token_id, to.map(|a| a.to_ss58check()), at.0, at.1],
+	)?;
+	Ok(())
+}
+
+fn market_event(
+	tx: &Transaction,
+	at: EventPosition,
+	event: &pallet_solar_market::Event<solar_node_runtime::Runtime>,
+) -> rusqlite::Result<()> {
+	use pallet_solar_market::Event::*;
+	let offer =
+		|kind: &str, seller: &AccountId, collection_id: u64, token_id: u64, price: Option<u128>| {
+			tx.execute(
+			"INSERT INTO market_offers (block_number, event_index, extrinsic_index, kind, seller, \
+			 collection_id, token_id, price) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+			params![
+				at.0,
+				at.1,
+				at.2,
+				kind,
+				seller.to_ss58check(),
+				collection_id as i64,
+				token_id as i64,
+				price.map(|p| p.to_string())
+			],
+		)
+		};
+	match event {
+		OfferCreated { seller, collection_id, token_id, price } =>
+			offer("created", seller, *collection_id, *token_id, Some(*price))?,
+		OfferUpdated { seller, collection_id, token_id, new_price, .. } =>
+			offer("updated", seller, *collection_id, *token_id, Some(*new_price))?,
+		OfferCancelled { seller, collection_id, token_id } =>
+			offer("cancelled", seller, *collection_id, *token_id, None)?,
+		Traded {
+			seller,
+			buyer,
+			collection_id,
+			token_id,
+			price,
+			commission_rate,
+			seller_income,
+			organiser_income,
+		} => tx.execute(
+			"INSERT INTO market_trades (block_number, event_index, extrinsic_index, \
+			 collection_id, token_id, seller, buyer, price, commission_rate, seller_income, \
+			 organiser_income) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+			params![
+				at.0,
+				at.1,
+				at.2,
+				*collection_id as i64,
+				*token_id as i64,
+				seller.to_ss58check(),
+				buyer.to_ss58check(),
+				price.to_string(),
+				commission_rate,
+				seller_income.to_string(),
+				organiser_income.to_string()
+			],
+		)?,
+		_ => return Ok(()),
+	};
+	Ok(())
+}
+
+/// Indexes finalized blocks as they come, after catching up from the first block missing in the
+/// database. Blocks after it that were indexed already are indexed again.
+///
+/// Blocks that cannot be read, such as those with pruned state, are skipped with a warning and
+/// retried on the next start. The task stops on database errors.
+pub async fn run(mut indexer: Indexer, client: Arc<FullClient>) {
+	let mut finality_notifications = client.finality_notification_stream();
+	let mut next = match indexer.first_missing() {
+		Ok(next) => next,
+		Err(e) => {
+			log::error!(target: "indexer", "Indexer stopped: {}", e);
+			return
+		},
+	};
+	log::info!(target: "indexer", "Indexing finalized blocks from #{}", next);
+
+	loop {
+		let finalized = client.info().finalized_number;
+		while next <= finalized {
+			match indexer.index_block(&client, next) {
+				Ok(()) => {},
+				Err(e @ Error::Chain(_)) => log::warn!(target: "indexer", "Skipping {}", e),
+				Err(e) => {
+					log::error!(target: "indexer", "Indexer stopped at block #{}: {}", next, e);
+					return
+				},
+			}
+			next += 1;
+		}
+		if finality_notifications.next().await.is_none() {
+			return
+		}
+	}
+}
+
+impl IndexCmd {
+	/// Indexes the blocks from `--from` to `--to`.
+	pub fn run(
+		&self,
+		client: Arc<FullClient>,
+		contract_metadata: Arc<MetadataStore>,
+	) -> sc_cli::Result<()> {
+		let to = self.to.unwrap_or_else(|| client.info().finalized_number);
+		if to < self.from {
+			return Err(format!("--to #{} is lower than --from #{}", to, self.from).into())
+		}
+		let mut indexer = Indexer::open(&self.db, contract_metadata)?;
+		for number in self.from..=to {
+			indexer.index_block(&client, number).map_err(|e| e.to_string())?;
+			if number % 1000 == 0 {
+				log::info!(target: "indexer", "Indexed up to block #{}", number);
+			}
+		}
+		log::info!(
+			target: "indexer",
+			"Indexed blocks #{} to #{} into {}",
+			self.from,
+			to,
+			self.db.display()
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_system::{EventRecord, Phase};
+	use solar_node_runtime::Runtime;
+	use sp_keyring::AccountKeyring::{Alice, Bob};
+
+	fn record(event: Event) -> EventRecord<Event, Hash> {
+		EventRecord { phase: Phase::ApplyExtrinsic(1), event, topics: vec![] }
+	}
+
+	fn transfer(from: AccountId, to: AccountId) -> EventRecord<Event, Hash> {
+		record(Event::Nft(pallet_solar_nft::Event::<Runtime>::Transfer {
+			from,
+			to,
+			collection_id: 0,
+			token_id: 7,
+		}))
+	}
+
+	fn owner(indexer: &Indexer) -> Option<String> {
+		indexer
+			.conn
+			.query_row(
+				"SELECT owner FROM nft_owners \
+				 WHERE collection_id = 0 AND token_id = 7 AND owner IS NOT NULL",
+				[],
+				|row| row.get(0),
+			)
+			.ok()
+	}
+
+	fn count(indexer: &Indexer, table: &str) -> u32 {
+		indexer
+			.conn
+			.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+			.unwrap()
+	}
+
+	#[test]
+	fn indexes_nft_and_market_events() {
+		let dir = tempfile::tempdir().unwrap();
+		let store = Arc::new(MetadataStore::open(None).unwrap());
+		let mut indexer = Indexer::open(&dir.path().join("index.sqlite"), store).unwrap();
+		let (alice, bob) = (Alice.to_account_id(), Bob.to_account_id());
+		assert_eq!(indexer.first_missing().unwrap(), 0);
+
+		let mint = record(Event::Nft(pallet_solar_nft::Event::<Runtime>::Minted {
+			owner: alice.clone(),
+			collection_id: 0,
+			token_id: 7,
+		}));
+		let trade = record(Event::Market(pallet_solar_market::Event::<Runtime>::Traded {
+			seller: alice.clone(),
+			buyer: bob.clone(),
+			collection_id: 0,
+			token_id: 7,
+			price: u128::MAX,
+			commission_rate: 5,
+			seller_income: 1,
+			organiser_income: 2,
+		}));
+		let records = [mint, transfer(alice.clone(), bob.clone()), trade];
+		indexer.write_block(1, Hash::zero(), Some(6000), &records, |_| None).unwrap();
+
+		// Block 0 is missing.
+		assert_eq!(indexer.first_missing().unwrap(), 0);
+		assert_eq!(count(&indexer, "nft_transfers"), 2);
+		assert_eq!(owner(&indexer), Some(bob.to_ss58check()));
+		let price: String = indexer
+			.conn
+			.query_row("SELECT price FROM market_trades", [], |row| row.get(0))
+			.unwrap();
+		assert_eq!(price, u128::MAX.to_string());
+
+		// Indexing an earlier block afterwards keeps the later owner, indexing a block again
+		// replaces its events.
+		indexer
+			.write_block(0, Hash::zero(), None, &[transfer(bob.clone(), alice.clone())], |_| None)
+			.unwrap();
+		assert_eq!(owner(&indexer), Some(bob.to_ss58check()));
+		assert_eq!(indexer.first_missing().unwrap(), 2);
+		indexer.write_block(1, Hash::zero(), Some(6000), &records, |_| None).unwrap();
+		assert_eq!(count(&indexer, "nft_transfers"), 3);
+		assert_eq!(count(&indexer, "market_trades"), 1);
+
+		let burn = record(Event::Nft(pallet_solar_nft::Event::<Runtime>::Burned {
+			caller: bob,
+			collection_id: 0,
+			token_id: 7,
+		}));
+		indexer.write_block(2, Hash::zero(), None, &[burn], |_| None).unwrap();
+		assert_eq!(owner(&indexer), None);
+
+		// Indexing resumes from the gap, not after the highest block.
+		indexer.write_block(5, Hash::zero(), None, &[], |_| None).unwrap();
+		assert_eq!(indexer.first_missing().unwrap(), 3);
+	}
+
+	#[test]
+	fn burned_tokens_stay_burned_when_earlier_blocks_are_indexed() {
+		let dir = tempfile::tempdir().unwrap();
+		let store = Arc::new(MetadataStore::open(None).unwrap());
+		let mut indexer = Indexer::open(&dir.path().join("index.sqlite"), store).unwrap();
+		let (alice, bob) = (Alice.to_account_id(), Bob.to_account_id());
+
+		let burn = record(Event::Nft(pallet_solar_nft::Event::<Runtime>::Burned {
+			caller: bob.clone(),
+			collection_id: 0,
+			token_id: 7,
+		}));
+		indexer.write_block(3, Hash::zero(), None, &[burn], |_| None).unwrap();
+		assert_eq!(owner(&indexer), None);
+
+		let mint = record(Event::Nft(pallet_solar_nft::Event::<Runtime>::Minted {
+			owner: alice.clone(),
+			collection_id: 0,
+			token_id: 7,
+		}));
+		indexer.write_block(1, Hash::zero(), None, &[mint], |_| None).unwrap();
+		indexer
+			.write_block(2, Hash::zero(), None, &[transfer(alice, bob)], |_| None)
+			.unwrap();
+		assert_eq!(owner(&indexer), None);
+		assert_eq!(count(&indexer, "nft_owners"), 1);
+	}
+}
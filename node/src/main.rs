@@ -7,6 +7,13 @@ mod service;
 mod cli;
 mod command;
 mod command_helper;
+mod contract_cmd;
+mod contract_events;
+mod contract_metadata;
+mod genesis;
+#[cfg(feature = "indexer")]
+mod indexer;
+mod remote_keystore;
 mod rpc;
 
 fn main() -> sc_cli::Result<()> {
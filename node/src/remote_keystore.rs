@@ -0,0 +1,571 @@
+//! A keystore whose keys live on a separate signer host, set with `--keystore-uri`.
+//!
+//! The node talks to the signer over HTTP with the JSON-RPC methods of [`SignerApi`]. Key types
+//! and crypto types are passed as their four-character IDs, such as `aura` and `sr25`, public keys,
+//! messages and signatures as hex-encoded bytes. The private keys never leave the signer, so the
+//! node can only sign with keys the signer holds.
+//!
+//! No secret is ever sent to the signer either: inserting a key from its SURI, as
+//! `author_insertKey` does, and generating a key from a seed, as `--alice` does, fail locally.
+//! Keys have to be generated on the signer, e.g. with `author_rotateKeys`, or put there by its
+//! operator.
+//!
+//! Requests are sent from a thread of their own, so the keystore can be used both from async tasks
+//! and from the blocking `SyncCryptoStore` API. VRF signing, which only BABE needs, is not
+//! supported.
+
+use futures::{
+	channel::{mpsc, oneshot},
+	future::BoxFuture,
+	FutureExt, StreamExt,
+};
+use jsonrpsee::{
+	core::{async_trait, Error as RpcError, RpcResult},
+	http_client::{HttpClient, HttpClientBuilder},
+	proc_macros::rpc,
+};
+use sp_core::{
+	crypto::{CryptoTypeId, CryptoTypePublicPair, KeyTypeId},
+	ecdsa, ed25519, sr25519, Bytes, H256,
+};
+use sp_keystore::{
+	vrf::{VRFSignature, VRFTranscriptData},
+	CryptoStore, Error, SyncCryptoStore,
+};
+use std::{future::Future, sync::Arc};
+
+/// The JSON-RPC methods a remote signer serves.
+#[rpc(client, server, namespace = "keystore")]
+pub trait SignerApi {
+	/// The `(crypto type, public key)` pairs of all keys of `key_type`.
+	#[method(name = "keys")]
+	fn keys(&self, key_type: String) -> RpcResult<Vec<(String, Bytes)>>;
+
+	/// Generates a new random key of `key_type` and returns its public key.
+	#[method(name = "generate")]
+	fn generate(&self, key_type: String, crypto_type: String) -> RpcResult<Bytes>;
+
+	/// Whether the signer holds all of the `(public key, key type)` pairs.
+	#[method(name = "hasKeys")]
+	fn has_keys(&self, keys: Vec<(Bytes, String)>) -> RpcResult<bool>;
+
+	/// Signs `message` with the key `public`, or returns `None` if the signer does not hold it.
+	#[method(name = "sign")]
+	fn sign(
+		&self,
+		key_type: String,
+		crypto_type: String,
+		public: Bytes,
+		message: Bytes,
+	) -> RpcResult<Option<Bytes>>;
+
+	/// Signs the 32-byte `hash` with the ECDSA key `public`, without hashing it again.
+	#[method(name = "ecdsaSignPrehashed")]
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: String,
+		public: Bytes,
+		hash: H256,
+	) -> RpcResult<Option<Bytes>>;
+}
+
+/// The four-character ID of a key or crypto type, as sent over the wire.
+pub fn id_to_string(id: [u8; 4]) -> String {
+	String::from_utf8_lossy(&id).into_owned()
+}
+
+/// Parses a four-character key or crypto type ID.
+pub fn id_from_str(id: &str) -> Result<[u8; 4], String> {
+	id.as_bytes()
+		.try_into()
+		.map_err(|_| format!("`{}` is not a four-character ID", id))
+}
+
+type Job = Box<dyn FnOnce(Arc<HttpClient>) -> BoxFuture<'static, ()> + Send>;
+
+/// A `CryptoStore` forwarding every request to a remote signer.
+pub struct RemoteKeystore {
+	jobs: mpsc::UnboundedSender<Job>,
+}
+
+impl RemoteKeystore {
+	/// Connects to the signer at `url` and checks that it answers.
+	pub fn open(url: &str) -> Result<Self, String> {
+		let client = HttpClientBuilder::default().build(url).map_err(|e| e.to_string())?;
+		let client = Arc::new(client);
+		let (jobs, mut queue) = mpsc::unbounded::<Job>();
+		let runtime = tokio::runtime::Builder::new_current_thread()
+			.enable_all()
+			.build()
+			.map_err(|e| e.to_string())?;
+		std::thread::Builder::new()
+			.name("remote-keystore".into())
+			.spawn(move || {
+				runtime.block_on(async move {
+					while let Some(job) = queue.next().await {
+						tokio::spawn(job(client.clone()));
+					}
+				})
+			})
+			.map_err(|e| e.to_string())?;
+
+		let keystore = Self { jobs };
+		futures::executor::block_on(
+			keystore.request(|client| async move { client.has_keys(vec![]).await }),
+		)
+		.map_err(|e| format!("Signer at {} is not available: {}", url, e))?;
+		Ok(keystore)
+	}
+
+	/// Runs `request` on the signer's client and waits for its result.
+	async fn request<R, F, Fut>(&self, request: F) -> Result<R, Error>
+	where
+		R: Send + 'static,
+		F: FnOnce(Arc<HttpClient>) -> Fut + Send + 'static,
+		Fut: Future<Output = Result<R, RpcError>> + Send + 'static,
+	{
+		let (result_tx, result_rx) = oneshot::channel();
+		let job: Job = Box::new(move |client| {
+			async move {
+				let _ = result_tx.send(request(client).await);
+			}
+			.boxed()
+		});
+		self.jobs.unbounded_send(job).map_err(|_| Error::Unavailable)?;
+		result_rx
+			.await
+			.map_err(|_| Error::Unavailable)?
+			.map_err(|e| Error::Other(e.to_string()))
+	}
+
+	async fn public_keys(&self, id: KeyTypeId, crypto_type: CryptoTypeId) -> Vec<Vec<u8>> {
+		let keys = CryptoStore::keys(self, id).await.unwrap_or_else(|e| {
+			log::warn!(target: "keystore", "Listing remote {:?} keys failed: {}", id, e);
+			vec![]
+		});
+		keys.into_iter()
+			.filter(|key| key.0 == crypto_type)
+			.map(|CryptoTypePublicPair(_, public)| public)
+			.collect()
+	}
+
+	async fn generate_new(
+		&self,
+		id: KeyTypeId,
+		crypto_type: CryptoTypeId,
+		seed: Option<&str>,
+	) -> Result<Vec<u8>, Error> {
+		if seed.is_some() {
+			return Err(Error::Other(
+				"The remote keystore does not send seeds to the signer, generate keys there".into(),
+			))
+		}
+		self.request(move |client| async move {
+			client.generate(id_to_string(id.0), id_to_string(crypto_type.0)).await
+		})
+		.await
+		.map(|public| public.0)
+	}
+}
+
+/// Converts the public keys of one crypto type.
+fn typed<P: for<'a> TryFrom<&'a [u8]>>(keys: Vec<Vec<u8>>) -> Vec<P> {
+	keys.iter().filter_map(|key| P::try_from(key.as_slice()).ok()).collect()
+}
+
+fn typed_new<P: for<'a> TryFrom<&'a [u8]>>(key: Vec<u8>) -> Result<P, Error> {
+	P::try_from(key.as_slice())
+		.map_err(|_| Error::ValidationError("Signer returned an invalid public key".into()))
+}
+
+#[async_trait]
+impl CryptoStore for RemoteKeystore {
+	async fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		typed(self.public_keys(id, sr25519::CRYPTO_ID).await)
+	}
+
+	async fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, Error> {
+		typed_new(self.generate_new(id, sr25519::CRYPTO_ID, seed).await?)
+	}
+
+	async fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		typed(self.public_keys(id, ed25519::CRYPTO_ID).await)
+	}
+
+	async fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, Error> {
+		typed_new(self.generate_new(id, ed25519::CRYPTO_ID, seed).await?)
+	}
+
+	async fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		typed(self.public_keys(id, ecdsa::CRYPTO_ID).await)
+	}
+
+	async fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, Error> {
+		typed_new(self.generate_new(id, ecdsa::CRYPTO_ID, seed).await?)
+	}
+
+	async fn insert_unknown(&self, id: KeyTypeId, _: &str, _: &[u8]) -> Result<(), ()> {
+		log::warn!(
+			target: "keystore",
+			"Not sending the secret of a {:?} key to the remote signer, insert it there",
+			id,
+		);
+		Err(())
+	}
+
+	async fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		let held = CryptoStore::keys(self, id).await?;
+		Ok(keys.into_iter().filter(|key| held.contains(key)).collect())
+	}
+
+	async fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		let keys = self
+			.request(move |client| async move { client.keys(id_to_string(id.0)).await })
+			.await?;
+		keys.into_iter()
+			.map(|(crypto_type, public)| {
+				let crypto_type = id_from_str(&crypto_type).map_err(Error::ValidationError)?;
+				Ok(CryptoTypePublicPair(CryptoTypeId(crypto_type), public.0))
+			})
+			.collect()
+	}
+
+	async fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let keys = public_keys
+			.iter()
+			.map(|(public, id)| (Bytes(public.clone()), id_to_string(id.0)))
+			.collect();
+		self.request(move |client| async move { client.has_keys(keys).await })
+			.await
+			.unwrap_or(false)
+	}
+
+	async fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> Result<Option<Vec<u8>>, Error> {
+		let (crypto_type, public) = (id_to_string(key.0 .0), Bytes(key.1.clone()));
+		let message = Bytes(msg.to_vec());
+		let signature = self
+			.request(move |client| async move {
+				client.sign(id_to_string(id.0), crypto_type, public, message).await
+			})
+			.await?;
+		Ok(signature.map(|signature| signature.0))
+	}
+
+	async fn sr25519_vrf_sign(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &sr25519::Public,
+		_transcript_data: VRFTranscriptData,
+	) -> Result<Option<VRFSignature>, Error> {
+		Err(Error::Other("VRF signing is not supported by the remote keystore".into()))
+	}
+
+	async fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, Error> {
+		let (public, hash) = (Bytes(public.0.to_vec()), H256(*msg));
+		let signature = self
+			.request(move |client| async move {
+				client.ecdsa_sign_prehashed(id_to_string(id.0), public, hash).await
+			})
+			.await?;
+		signature
+			.map(|signature| {
+				ecdsa::Signature::try_from(&signature.0[..]).map_err(|_| {
+					Error::ValidationError("Signer returned an invalid signature".into())
+				})
+			})
+			.transpose()
+	}
+}
+
+impl SyncCryptoStore for RemoteKeystore {
+	fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		futures::executor::block_on(CryptoStore::sr25519_public_keys(self, id))
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, Error> {
+		futures::executor::block_on(CryptoStore::sr25519_generate_new(self, id, seed))
+	}
+
+	fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		futures::executor::block_on(CryptoStore::ed25519_public_keys(self, id))
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, Error> {
+		futures::executor::block_on(CryptoStore::ed25519_generate_new(self, id, seed))
+	}
+
+	fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		futures::executor::block_on(CryptoStore::ecdsa_public_keys(self, id))
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, Error> {
+		futures::executor::block_on(CryptoStore::ecdsa_generate_new(self, id, seed))
+	}
+
+	fn insert_unknown(&self, key_type: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		futures::executor::block_on(CryptoStore::insert_unknown(self, key_type, suri, public))
+	}
+
+	fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		futures::executor::block_on(CryptoStore::supported_keys(self, id, keys))
+	}
+
+	fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		futures::executor::block_on(CryptoStore::keys(self, id))
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		futures::executor::block_on(CryptoStore::has_keys(self, public_keys))
+	}
+
+	fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> Result<Option<Vec<u8>>, Error> {
+		futures::executor::block_on(CryptoStore::sign_with(self, id, key, msg))
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		transcript_data: VRFTranscriptData,
+	) -> Result<Option<VRFSignature>, Error> {
+		futures::executor::block_on(CryptoStore::sr25519_vrf_sign(
+			self,
+			key_type,
+			public,
+			transcript_data,
+		))
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, Error> {
+		futures::executor::block_on(CryptoStore::ecdsa_sign_prehashed(self, id, public, msg))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use jsonrpsee::{
+		core::Error as JsonRpseeError,
+		http_server::{HttpServerBuilder, HttpServerHandle},
+	};
+	use sp_core::Pair;
+	use sp_keystore::testing::KeyStore;
+
+	const AURA: KeyTypeId = KeyTypeId(*b"aura");
+	const GRANDPA: KeyTypeId = KeyTypeId(*b"gran");
+
+	/// A signer serving an in-memory keystore.
+	struct MockSigner(KeyStore);
+
+	fn key_type(key_type: &str) -> RpcResult<KeyTypeId> {
+		id_from_str(key_type).map(KeyTypeId).map_err(JsonRpseeError::Custom)
+	}
+
+	fn crypto_type(crypto_type: &str) -> RpcResult<CryptoTypeId> {
+		id_from_str(crypto_type).map(CryptoTypeId).map_err(JsonRpseeError::Custom)
+	}
+
+	fn keystore_error(e: Error) -> JsonRpseeError {
+		JsonRpseeError::Custom(e.to_string())
+	}
+
+	impl SignerApiServer for MockSigner {
+		fn keys(&self, id: String) -> RpcResult<Vec<(String, Bytes)>> {
+			let keys = SyncCryptoStore::keys(&self.0, key_type(&id)?).map_err(keystore_error)?;
+			Ok(keys
+				.into_iter()
+				.map(|CryptoTypePublicPair(crypto_type, public)| {
+					(id_to_string(crypto_type.0), Bytes(public))
+				})
+				.collect())
+		}
+
+		fn generate(&self, id: String, crypto: String) -> RpcResult<Bytes> {
+			let id = key_type(&id)?;
+			let public = match crypto_type(&crypto)? {
+				sr25519::CRYPTO_ID =>
+					SyncCryptoStore::sr25519_generate_new(&self.0, id, None).map(|p| p.0.to_vec()),
+				ed25519::CRYPTO_ID =>
+					SyncCryptoStore::ed25519_generate_new(&self.0, id, None).map(|p| p.0.to_vec()),
+				ecdsa::CRYPTO_ID =>
+					SyncCryptoStore::ecdsa_generate_new(&self.0, id, None).map(|p| p.0.to_vec()),
+				_ => return Err(JsonRpseeError::Custom(format!("unknown crypto type {}", crypto))),
+			};
+			public.map(Bytes).map_err(keystore_error)
+		}
+
+		fn has_keys(&self, keys: Vec<(Bytes, String)>) -> RpcResult<bool> {
+			let keys = keys
+				.into_iter()
+				.map(|(public, id)| Ok((public.0, key_type(&id)?)))
+				.collect::<RpcResult<Vec<_>>>()?;
+			Ok(SyncCryptoStore::has_keys(&self.0, &keys))
+		}
+
+		fn sign(
+			&self,
+			id: String,
+			crypto: String,
+			public: Bytes,
+			message: Bytes,
+		) -> RpcResult<Option<Bytes>> {
+			let key = CryptoTypePublicPair(crypto_type(&crypto)?, public.0);
+			SyncCryptoStore::sign_with(&self.0, key_type(&id)?, &key, &message)
+				.map(|signature| signature.map(Bytes))
+				.map_err(keystore_error)
+		}
+
+		fn ecdsa_sign_prehashed(
+			&self,
+			id: String,
+			public: Bytes,
+			hash: H256,
+		) -> RpcResult<Option<Bytes>> {
+			let public = ecdsa::Public::try_from(&public[..])
+				.map_err(|_| JsonRpseeError::Custom("invalid public key".into()))?;
+			SyncCryptoStore::ecdsa_sign_prehashed(&self.0, key_type(&id)?, &public, &hash.0)
+				.map(|signature| signature.map(|s| Bytes(s.0.to_vec())))
+				.map_err(keystore_error)
+		}
+	}
+
+	async fn start_signer() -> (HttpServerHandle, String) {
+		start_signer_with(KeyStore::new()).await
+	}
+
+	async fn start_signer_with(keystore: KeyStore) -> (HttpServerHandle, String) {
+		let server = HttpServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+		let url = format!("http://{}", server.local_addr().unwrap());
+		let handle = server.start(MockSigner(keystore).into_rpc()).unwrap();
+		(handle, url)
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn signs_with_remote_keys() {
+		let (_signer, url) = start_signer().await;
+		let keystore = RemoteKeystore::open(&url).unwrap();
+
+		let aura = SyncCryptoStore::sr25519_generate_new(&keystore, AURA, None).unwrap();
+		let grandpa = CryptoStore::ed25519_generate_new(&keystore, GRANDPA, None).await.unwrap();
+		assert_eq!(SyncCryptoStore::sr25519_public_keys(&keystore, AURA), vec![aura]);
+		assert_eq!(CryptoStore::ed25519_public_keys(&keystore, GRANDPA).await, vec![grandpa]);
+		assert!(SyncCryptoStore::sr25519_public_keys(&keystore, GRANDPA).is_empty());
+		assert!(SyncCryptoStore::has_keys(
+			&keystore,
+			&[(aura.0.to_vec(), AURA), (grandpa.0.to_vec(), GRANDPA)]
+		));
+
+		let message = b"block header";
+		let signature = SyncCryptoStore::sign_with(&keystore, AURA, &aura.into(), message)
+			.unwrap()
+			.unwrap();
+		let signature = sr25519::Signature::try_from(&signature[..]).unwrap();
+		assert!(sr25519::Pair::verify(&signature, message, &aura));
+
+		let signature = CryptoStore::sign_with(&keystore, GRANDPA, &grandpa.into(), message)
+			.await
+			.unwrap()
+			.unwrap();
+		let signature = ed25519::Signature::try_from(&signature[..]).unwrap();
+		assert!(ed25519::Pair::verify(&signature, message, &grandpa));
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn unknown_keys_are_not_used() {
+		let signer_keystore = KeyStore::new();
+		SyncCryptoStore::insert_unknown(&signer_keystore, AURA, "//Alice", &sr25519_alice().0)
+			.unwrap();
+		let (_signer, url) = start_signer_with(signer_keystore).await;
+		let keystore = RemoteKeystore::open(&url).unwrap();
+		let (pair, _) = sr25519::Pair::generate();
+		let public = pair.public();
+
+		assert!(!SyncCryptoStore::has_keys(&keystore, &[(public.0.to_vec(), AURA)]));
+		assert!(matches!(
+			SyncCryptoStore::sign_with(&keystore, AURA, &public.into(), b"msg"),
+			Ok(None)
+		));
+		assert!(SyncCryptoStore::supported_keys(&keystore, AURA, vec![public.into()])
+			.unwrap()
+			.is_empty());
+
+		// Keys the operator put on the signer are used.
+		assert!(SyncCryptoStore::has_keys(&keystore, &[(sr25519_alice().0.to_vec(), AURA)]));
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn secrets_are_not_sent_to_the_signer() {
+		let (_signer, url) = start_signer().await;
+		let keystore = RemoteKeystore::open(&url).unwrap();
+
+		assert!(SyncCryptoStore::insert_unknown(&keystore, AURA, "//Alice", &sr25519_alice().0)
+			.is_err());
+		assert!(SyncCryptoStore::sr25519_generate_new(&keystore, AURA, Some("//Alice")).is_err());
+		assert!(CryptoStore::ed25519_generate_new(&keystore, GRANDPA, Some("//Alice"))
+			.await
+			.is_err());
+		assert!(SyncCryptoStore::keys(&keystore, AURA).unwrap().is_empty());
+		assert!(SyncCryptoStore::keys(&keystore, GRANDPA).unwrap().is_empty());
+	}
+
+	#[test]
+	fn fails_to_open_without_signer() {
+		assert!(RemoteKeystore::open("http://127.0.0.1:1").is_err());
+	}
+
+	fn sr25519_alice() -> sr25519::Public {
+		sr25519::Pair::from_string("//Alice", None).unwrap().public()
+	}
+}
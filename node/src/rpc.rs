@@ -7,8 +7,10 @@
 
 use std::sync::Arc;
 
+use futures::channel::mpsc;
 use jsonrpsee::RpcModule;
-use sc_client_api::BlockBackend;
+use sc_client_api::{Backend, BlockBackend, StorageProvider};
+use sc_consensus_manual_seal::EngineCommand;
 use sc_rpc::dev::{Dev, DevApiServer};
 use sc_transaction_pool_api::TransactionPool;
 use solar_node_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Index};
@@ -16,6 +18,8 @@ use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 
+use crate::contract_metadata::MetadataStore;
+
 pub use sc_rpc_api::DenyUnsafe;
 
 /// Full client dependencies.
@@ -26,29 +30,41 @@ pub struct FullDeps<C, P> {
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Channel to the manual seal task, with `--dev-seal`.
+	pub command_sink: Option<mpsc::Sender<EngineCommand<Hash>>>,
+	/// Registered ink! metadata to decode contract events with.
+	pub contract_metadata: Arc<MetadataStore>,
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(
+pub fn create_full<C, P, B>(
 	deps: FullDeps<C, P>,
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>,
 	C: BlockBackend<Block>,
+	C: StorageProvider<Block, B>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_solar_block_reward_rpc::BlockRewardRuntimeApi<Block, AccountId, Balance>,
+	C::Api: pallet_solar_nft_rpc::NftRuntimeApi<Block, AccountId>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
+	B: Backend<Block> + Send + Sync + 'static,
 {
+	use crate::contract_events::{ContractEvents, ContractEventsApiServer};
 	use pallet_contracts_rpc::{Contracts, ContractsApiServer};
+	use pallet_solar_block_reward_rpc::{BlockReward, BlockRewardApiServer};
+	use pallet_solar_nft_rpc::{Nft, NftApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
 	let mut module = RpcModule::new(());
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	let FullDeps { client, pool, deny_unsafe, command_sink, contract_metadata } = deps;
 
 	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
@@ -61,8 +77,22 @@ where
 	// Contracts RPC API extension
 	module.merge(Contracts::new(client.clone()).into_rpc())?;
 
+	// Contract event decoding RPC API extension
+	module.merge(ContractEvents::new(client.clone(), contract_metadata, deny_unsafe).into_rpc())?;
+
+	// Block reward RPC API extension
+	module.merge(BlockReward::<_, _, Balance>::new(client.clone()).into_rpc())?;
+
+	// NFT RPC API extension
+	module.merge(Nft::new(client.clone()).into_rpc())?;
+
 	// Dev RPC API extension
 	module.merge(Dev::new(client, deny_unsafe).into_rpc())?;
 
+	// Manual seal RPC API extension, to create and finalize blocks with `--dev-seal`
+	if let Some(command_sink) = command_sink {
+		module.merge(ManualSeal::new(command_sink).into_rpc())?;
+	}
+
 	Ok(module)
 }
@@ -1,15 +1,23 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
-use sc_client_api::{BlockBackend, ExecutorProvider};
+use crate::{cli::Sealing, contract_metadata::MetadataStore, remote_keystore::RemoteKeystore};
+use futures::{channel::mpsc, stream, FutureExt, StreamExt};
+use futures_timer::Delay;
+use sc_client_api::{BlockBackend, ExecutorProvider, StorageProvider};
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
+use sc_consensus_manual_seal::{
+	consensus::aura::AuraConsensusDataProvider, EngineCommand, ManualSealParams,
+};
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_finality_grandpa::SharedVoterState;
-use sc_keystore::LocalKeystore;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
+use sc_transaction_pool_api::TransactionPool;
 use solar_node_runtime::{self, opaque::Block, RuntimeApi};
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
-use std::{sync::Arc, time::Duration};
+use sp_core::{storage::StorageKey, twox_128, H256};
+use sp_runtime::generic::BlockId;
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 // Our native executor instance.
 pub struct ExecutorDispatch;
@@ -58,10 +66,6 @@ pub fn new_partial(
 	>,
 	ServiceError,
 > {
-	if config.keystore_remote.is_some() {
-		return Err(ServiceError::Other(format!("Remote Keystores are not supported.")))
-	}
-
 	let telemetry = config
 		.telemetry_endpoints
 		.clone()
@@ -149,15 +153,38 @@ pub fn new_partial(
 	})
 }
 
-fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
-	// FIXME: here would the concrete keystore be built,
-	//        must return a concrete type (NOT `LocalKeystore`) that
-	//        implements `CryptoStore` and `SyncCryptoStore`
-	Err("Remote Keystore not supported.")
+fn remote_keystore(url: &String) -> Result<Arc<RemoteKeystore>, String> {
+	RemoteKeystore::open(url).map(Arc::new)
+}
+
+/// The timestamp `pallet_timestamp` stored in block `hash`, if any.
+pub(crate) fn block_timestamp(client: &FullClient, hash: H256) -> Option<u64> {
+	let key = StorageKey([twox_128(b"Timestamp"), twox_128(b"Now")].concat());
+	let value = client.storage(&BlockId::Hash(hash), &key).ok()??;
+	Some(u64::from_le_bytes(value.0.try_into().ok()?))
+}
+
+/// The ink! metadata registered through `solar_registerContractMetadata`, kept next to the
+/// keystore of the chain.
+pub fn contract_metadata(config: &Configuration) -> Result<Arc<MetadataStore>, ServiceError> {
+	let dir = config
+		.base_path
+		.as_ref()
+		.map(|base_path| base_path.config_dir(config.chain_spec.id()).join("contract-metadata"));
+	MetadataStore::open(dir).map(Arc::new).map_err(ServiceError::Other)
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+///
+/// With `sealing`, blocks are sealed by `sc-consensus-manual-seal` as the mode says, or on request
+/// through the `engine_createBlock` RPC, instead of by Aura, and finalized without Grandpa.
+///
+/// With `index_db`, finalized blocks are indexed into that SQLite database by the `indexer`.
+pub fn new_full(
+	mut config: Configuration,
+	sealing: Option<Sealing>,
+	#[cfg_attr(not(feature = "indexer"), allow(unused_variables))] index_db: Option<PathBuf>,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -222,13 +249,30 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	// Commands of the `engine_*` RPCs for the manual seal task.
+	let (command_sink, rpc_commands) = match sealing {
+		Some(_) => {
+			let (sink, commands) = mpsc::channel(1024);
+			(Some(sink), Some(commands))
+		},
+		None => (None, None),
+	};
+
+	let contract_metadata = contract_metadata(&config)?;
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
+		let contract_metadata = contract_metadata.clone();
 
 		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				command_sink: command_sink.clone(),
+				contract_metadata: contract_metadata.clone(),
+			};
 			crate::rpc::create_full(deps).map_err(Into::into)
 		})
 	};
@@ -246,6 +290,82 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		telemetry: telemetry.as_mut(),
 	})?;
 
+	#[cfg(feature = "indexer")]
+	if let Some(path) = index_db {
+		let indexer =
+			crate::indexer::Indexer::open(&path, contract_metadata).map_err(ServiceError::Other)?;
+		// The indexer writes to SQLite synchronously.
+		task_manager.spawn_handle().spawn_blocking(
+			"indexer",
+			None,
+			crate::indexer::run(indexer, client.clone()),
+		);
+	}
+
+	if let Some((sealing, rpc_commands)) = sealing.zip(rpc_commands) {
+		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+			task_manager.spawn_handle(),
+			client.clone(),
+			transaction_pool.clone(),
+			prometheus_registry.as_ref(),
+			telemetry.as_ref().map(|x| x.handle()),
+		);
+
+		let seal = |create_empty| EngineCommand::SealNewBlock {
+			create_empty,
+			finalize: true,
+			parent_hash: None,
+			sender: None,
+		};
+		let commands_stream = match sealing {
+			Sealing::Manual => rpc_commands.boxed(),
+			Sealing::Instant => stream::select(
+				rpc_commands,
+				transaction_pool.import_notification_stream().map(move |_| seal(false)),
+			)
+			.boxed(),
+			Sealing::Interval(millis) => stream::select(
+				rpc_commands,
+				stream::unfold((), move |()| {
+					Delay::new(Duration::from_millis(millis)).map(move |()| Some((seal(true), ())))
+				}),
+			)
+			.boxed(),
+		};
+
+		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+		let timestamp_client = client.clone();
+		let manual_seal = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env: proposer_factory,
+			client: client.clone(),
+			pool: transaction_pool,
+			commands_stream,
+			select_chain,
+			consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(client))),
+			create_inherent_data_providers: move |parent, ()| {
+				// `pallet_aura` requires every block to be in a later slot than its parent, so
+				// blocks sealed faster than the slot duration get timestamps ahead of the clock.
+				let parent_timestamp = block_timestamp(&timestamp_client, parent).unwrap_or(0);
+				async move {
+					let now = sp_timestamp::Timestamp::current().as_millis();
+					let timestamp = now.max(parent_timestamp + slot_duration.as_millis());
+					Ok(sp_timestamp::InherentDataProvider::new(timestamp.into()))
+				}
+			},
+		});
+
+		// The manual seal task is essential, like the Aura authoring task.
+		task_manager.spawn_essential_handle().spawn_blocking(
+			"manual-seal",
+			Some("block-authoring"),
+			manual_seal,
+		);
+
+		network_starter.start_network();
+		return Ok(task_manager)
+	}
+
 	if role.is_authority() {
 		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
@@ -0,0 +1,19 @@
+//! Runtime API definition for the block reward RPC.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	/// The API to query block rewards.
+	pub trait BlockRewardApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// The reward for the author of the next block.
+		fn next_block_reward() -> Balance;
+
+		/// The rewards `who` has received in total.
+		fn total_rewards(who: AccountId) -> Balance;
+	}
+}
@@ -0,0 +1,108 @@
+//! RPC interface for the block reward pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+pub use pallet_solar_block_reward_rpc_runtime_api::BlockRewardApi as BlockRewardRuntimeApi;
+use serde::de::DeserializeOwned;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+#[rpc(client, server)]
+pub trait BlockRewardApi<BlockHash, AccountId> {
+	/// The reward for the author of the block after `at`.
+	#[method(name = "solar_nextBlockReward")]
+	fn next_block_reward(&self, at: Option<BlockHash>) -> RpcResult<NumberOrHex>;
+
+	/// The rewards `who` has received in total up to `at`.
+	#[method(name = "solar_totalBlockRewards")]
+	fn total_rewards(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<NumberOrHex>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The balance does not fit into `NumberOrHex`.
+	BalanceOverflow,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::BalanceOverflow => 2,
+		}
+	}
+}
+
+/// Provides RPC methods to query block rewards.
+pub struct BlockReward<C, B, Balance> {
+	client: Arc<C>,
+	_marker: PhantomData<(B, Balance)>,
+}
+
+impl<C, B, Balance> BlockReward<C, B, Balance> {
+	/// Creates a new instance of the block reward RPC.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn into_number<Balance: TryInto<NumberOrHex>>(balance: Balance) -> RpcResult<NumberOrHex> {
+	balance.try_into().map_err(|_| {
+		CallError::Custom(ErrorObject::owned(
+			Error::BalanceOverflow.into(),
+			"Balance does not fit into NumberOrHex.",
+			None::<()>,
+		))
+		.into()
+	})
+}
+
+fn runtime_error(e: impl std::fmt::Display, message: &str) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		message.to_string(),
+		Some(e.to_string()),
+	))
+	.into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance> BlockRewardApiServer<<Block as BlockT>::Hash, AccountId>
+	for BlockReward<C, Block, Balance>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BlockRewardRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + TryInto<NumberOrHex> + Send + Sync + 'static,
+{
+	fn next_block_reward(&self, at: Option<Block::Hash>) -> RpcResult<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let reward = api
+			.next_block_reward(&at)
+			.map_err(|e| runtime_error(e, "Unable to query the block reward."))?;
+		into_number(reward)
+	}
+
+	fn total_rewards(&self, who: AccountId, at: Option<Block::Hash>) -> RpcResult<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let total = api
+			.total_rewards(&at, who)
+			.map_err(|e| runtime_error(e, "Unable to query the total rewards."))?;
+		into_number(total)
+	}
+}
@@ -0,0 +1,40 @@
+//! Benchmarks for the Solar Block Reward pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::{EnsureOrigin, Get};
+
+benchmarks! {
+	// The worst case mints to an author that does not exist yet.
+	reward_author {
+		let blocks_per_year: BalanceOf<T> = T::BlocksPerYear::get().max(1u32.into()).saturated_into();
+		let funder: T::AccountId = account("funder", 0, 0);
+		let issuance = T::Currency::minimum_balance()
+			.saturating_mul(blocks_per_year)
+			.saturating_mul(100u32.into());
+		T::Currency::make_free_balance_be(&funder, issuance);
+		InflationSchedule::<T>::put(
+			BoundedVec::try_from(vec![(T::BlockNumber::zero(), Perbill::one())]).unwrap(),
+		);
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+		let author: T::AccountId = account("author", 0, 0);
+	}: {
+		Pallet::<T>::reward(author.clone());
+	}
+	verify {
+		assert!(!TotalRewards::<T>::get(&author).is_zero());
+	}
+
+	set_schedule {
+		let s in 1 .. T::MaxScheduleLen::get();
+		let schedule: Vec<_> = (0..s).map(|i| (i.into(), Perbill::from_percent(5))).collect();
+		let origin = T::UpdateOrigin::successful_origin();
+	}: _<T::Origin>(origin, schedule)
+	verify {
+		assert_eq!(InflationSchedule::<T>::get().len(), s as usize);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,201 @@
+//! # Solar Block Reward Pallet
+//!
+//! Mints a reward for the author of every block, as the `EventHandler` of `pallet_authorship`.
+//!
+//! The reward follows an inflation schedule: a list of `(block, rate)` entries, each giving the
+//! annual inflation rate from that block on. A block's reward is the current rate applied to the
+//! total issuance, divided by `BlocksPerYear`. The schedule is set at genesis and changed by
+//! `UpdateOrigin`; without an entry in effect no rewards are minted.
+//!
+//! Every reward is announced with a `Rewarded` event and added to the author's `TotalRewards`,
+//! which the `BlockRewardApi` runtime API exposes together with the reward of the next block.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use frame_support::{
+	traits::{Currency, Imbalance},
+	BoundedVec,
+};
+use sp_runtime::{traits::Zero, PerThing, Perbill, SaturatedConversion, Saturating};
+use sp_std::prelude::*;
+pub use weights::WeightInfo;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency rewards are minted in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The number of blocks the annual inflation rate is spread over.
+		#[pallet::constant]
+		type BlocksPerYear: Get<Self::BlockNumber>;
+
+		/// The origin allowed to change the inflation schedule.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of entries in the inflation schedule.
+		#[pallet::constant]
+		type MaxScheduleLen: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Annual inflation rates by the block they apply from, in ascending block order.
+	#[pallet::storage]
+	#[pallet::getter(fn inflation_schedule)]
+	pub type InflationSchedule<T: Config> =
+		StorageValue<_, BoundedVec<(T::BlockNumber, Perbill), T::MaxScheduleLen>, ValueQuery>;
+
+	/// The rewards an author has received in total.
+	#[pallet::storage]
+	#[pallet::getter(fn total_rewards)]
+	pub type TotalRewards<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The initial inflation schedule.
+		pub schedule: Vec<(T::BlockNumber, Perbill)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { schedule: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let schedule = Pallet::<T>::checked_schedule(self.schedule.clone())
+				.expect("genesis inflation schedule must be sorted and within MaxScheduleLen; qed");
+			InflationSchedule::<T>::put(schedule);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The author of the block was rewarded.
+		Rewarded { author: T::AccountId, amount: BalanceOf<T> },
+		/// The inflation schedule was changed.
+		ScheduleSet { schedule: Vec<(T::BlockNumber, Perbill)> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The schedule entries are not in strictly ascending block order.
+		UnsortedSchedule,
+		/// The schedule has more than `MaxScheduleLen` entries.
+		ScheduleTooLong,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			// The reward is paid from `pallet_authorship`, whose event handler carries no weight
+			// of its own.
+			T::WeightInfo::reward_author()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Replaces the inflation schedule.
+		#[pallet::weight(T::WeightInfo::set_schedule(schedule.len() as u32))]
+		pub fn set_schedule(
+			origin: OriginFor<T>,
+			schedule: Vec<(T::BlockNumber, Perbill)>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			InflationSchedule::<T>::put(Self::checked_schedule(schedule.clone())?);
+
+			Self::deposit_event(Event::ScheduleSet { schedule });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The annual inflation rate in effect at block `n`.
+	pub fn inflation_at(n: T::BlockNumber) -> Perbill {
+		Self::inflation_schedule()
+			.iter()
+			.rev()
+			.find(|(from, _)| *from <= n)
+			.map(|(_, rate)| *rate)
+			.unwrap_or_default()
+	}
+
+	/// The reward for the author of block `n`, given the current total issuance.
+	pub fn block_reward(n: T::BlockNumber) -> BalanceOf<T> {
+		let blocks_per_year: BalanceOf<T> = T::BlocksPerYear::get().saturated_into();
+		if blocks_per_year.is_zero() {
+			return Zero::zero()
+		}
+		Self::inflation_at(n).mul_floor(T::Currency::total_issuance()) / blocks_per_year
+	}
+
+	fn checked_schedule(
+		schedule: Vec<(T::BlockNumber, Perbill)>,
+	) -> Result<BoundedVec<(T::BlockNumber, Perbill), T::MaxScheduleLen>, Error<T>> {
+		if schedule.windows(2).any(|w| w[0].0 >= w[1].0) {
+			return Err(Error::<T>::UnsortedSchedule)
+		}
+		schedule.try_into().map_err(|_| Error::<T>::ScheduleTooLong)
+	}
+
+	fn reward(author: T::AccountId) {
+		let amount = Self::block_reward(frame_system::Pallet::<T>::block_number());
+		if amount.is_zero() {
+			return
+		}
+
+		// Rewards below the existential deposit of a new account are not minted.
+		let minted = T::Currency::deposit_creating(&author, amount);
+		let amount = minted.peek();
+		if amount.is_zero() {
+			return
+		}
+		TotalRewards::<T>::mutate(&author, |total| *total = total.saturating_add(amount));
+		Self::deposit_event(Event::Rewarded { author, amount });
+	}
+}
+
+impl<T: Config> pallet_authorship::EventHandler<T::AccountId, T::BlockNumber> for Pallet<T> {
+	fn note_author(author: T::AccountId) {
+		Self::reward(author)
+	}
+
+	fn note_uncle(_author: T::AccountId, _age: T::BlockNumber) {}
+}
@@ -0,0 +1,76 @@
+//! Unit tests for the Solar Block Reward pallet.
+
+use crate::{mock::*, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use pallet_authorship::EventHandler;
+use sp_runtime::{DispatchError, Perbill};
+
+#[test]
+fn inflation_follows_schedule() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(BlockReward::inflation_at(0), Perbill::from_percent(10));
+		assert_eq!(BlockReward::inflation_at(9), Perbill::from_percent(10));
+		assert_eq!(BlockReward::inflation_at(10), Perbill::from_percent(5));
+		assert_eq!(BlockReward::inflation_at(1_000), Perbill::from_percent(5));
+	});
+}
+
+#[test]
+fn author_is_rewarded() {
+	new_test_ext().execute_with(|| {
+		// 10% of 10_000 spread over 100 blocks.
+		BlockReward::note_author(BOB);
+		System::assert_last_event(Event::<Test>::Rewarded { author: BOB, amount: 10 }.into());
+		assert_eq!(Balances::free_balance(BOB), 10);
+		assert_eq!(Balances::total_issuance(), 10_010);
+
+		// 5% of 10_010 spread over 100 blocks.
+		System::set_block_number(10);
+		BlockReward::note_author(BOB);
+		System::assert_last_event(Event::<Test>::Rewarded { author: BOB, amount: 5 }.into());
+		assert_eq!(BlockReward::total_rewards(BOB), 15);
+	});
+}
+
+#[test]
+fn no_reward_without_schedule() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlockReward::set_schedule(Origin::root(), vec![]));
+		assert_eq!(BlockReward::block_reward(1), 0);
+
+		BlockReward::note_author(BOB);
+		assert_eq!(Balances::free_balance(BOB), 0);
+		assert_eq!(BlockReward::total_rewards(BOB), 0);
+	});
+}
+
+#[test]
+fn set_schedule_works() {
+	new_test_ext().execute_with(|| {
+		let schedule = vec![(0, Perbill::from_percent(8)), (50, Perbill::from_percent(4))];
+		assert_noop!(
+			BlockReward::set_schedule(Origin::signed(ALICE), schedule.clone()),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(BlockReward::set_schedule(Origin::root(), schedule.clone()));
+		System::assert_last_event(Event::<Test>::ScheduleSet { schedule }.into());
+		assert_eq!(BlockReward::inflation_at(49), Perbill::from_percent(8));
+		assert_eq!(BlockReward::inflation_at(50), Perbill::from_percent(4));
+	});
+}
+
+#[test]
+fn set_schedule_rejects_bad_schedule() {
+	new_test_ext().execute_with(|| {
+		let rate = Perbill::from_percent(1);
+		assert_noop!(
+			BlockReward::set_schedule(Origin::root(), vec![(5, rate), (5, rate)]),
+			Error::<Test>::UnsortedSchedule
+		);
+		assert_noop!(
+			BlockReward::set_schedule(Origin::root(), (0..5).map(|n| (n, rate)).collect()),
+			Error::<Test>::ScheduleTooLong
+		);
+	});
+}
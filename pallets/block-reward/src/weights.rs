@@ -0,0 +1,54 @@
+//! Weights for pallet_solar_block_reward
+//!
+//! PLACEHOLDER, not benchmark output: hand-written weights, a flat cost plus the storage accesses
+//! of each extrinsic. `scripts/benchmark.sh` measures the benchmarks in `benchmarking.rs`; copy
+//! its output for this pallet here once it has run on the reference machine.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_solar_block_reward.
+pub trait WeightInfo {
+	fn reward_author() -> Weight;
+	fn set_schedule(s: u32, ) -> Weight;
+}
+
+/// Placeholder weights for pallet_solar_block_reward, see the module docs.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: BlockReward InflationSchedule (r:1 w:0)
+	// Storage: Balances TotalIssuance (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: BlockReward TotalRewards (r:1 w:1)
+	fn reward_author() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: BlockReward InflationSchedule (r:0 w:1)
+	fn set_schedule(s: u32, ) -> Weight {
+		(15_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((100_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn reward_author() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn set_schedule(s: u32, ) -> Weight {
+		(15_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((100_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
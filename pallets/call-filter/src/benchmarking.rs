@@ -0,0 +1,37 @@
+//! Benchmarks for the Solar Call Filter pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::benchmarks;
+use frame_support::traits::EnsureOrigin;
+
+/// A pallet or function name of `MaxNameLen` bytes.
+fn max_name<T: Config>(byte: u8) -> Vec<u8> {
+	vec![byte; T::MaxNameLen::get() as usize]
+}
+
+benchmarks! {
+	allow_call {
+		let origin = T::ManagerOrigin::successful_origin();
+		let (pallet_name, function_name) = (max_name::<T>(b'p'), max_name::<T>(b'f'));
+	}: _<T::Origin>(origin, pallet_name.clone(), function_name.clone())
+	verify {
+		let (pallet_name, function_name) =
+			Pallet::<T>::bounded_names(pallet_name, function_name).unwrap();
+		assert!(AllowedCalls::<T>::contains_key(&pallet_name, &function_name));
+	}
+
+	disallow_call {
+		let origin = T::ManagerOrigin::successful_origin();
+		let (pallet_name, function_name) = (max_name::<T>(b'p'), max_name::<T>(b'f'));
+		let (bounded_pallet, bounded_function) =
+			Pallet::<T>::bounded_names(pallet_name.clone(), function_name.clone()).unwrap();
+		AllowedCalls::<T>::insert(&bounded_pallet, &bounded_function, ());
+	}: _<T::Origin>(origin, pallet_name, function_name)
+	verify {
+		assert!(!AllowedCalls::<T>::contains_key(&bounded_pallet, &bounded_function));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,217 @@
+//! # Solar Call Filter Pallet
+//!
+//! Keeps the whitelist of runtime calls that contracts may dispatch through
+//! `seal_call_runtime`. Calls are identified by `(pallet_name, function_name)` as reported by
+//! `GetCallMetadata`, so the whitelist survives changes to pallet or call indices.
+//!
+//! [`Whitelist`] is meant to be used as `pallet_contracts::Config::CallFilter`. The list is
+//! seeded from `Config::InitialWhitelist` at genesis, or on the runtime upgrade that introduces
+//! the pallet, and afterwards changed only by `Config::ManagerOrigin`.
+//!
+//! Dispatchables exposed to contracts must stay stable, because changing them would break
+//! already deployed contracts. Only whitelist calls whose encoding is not expected to change.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use frame_support::{
+	dispatch::GetCallMetadata,
+	traits::{Contains, Get},
+	BoundedVec,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+pub use weights::WeightInfo;
+
+/// Bounded name of a pallet or function.
+pub type NameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLen>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin allowed to change the whitelist.
+		type ManagerOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum length of a pallet or function name.
+		#[pallet::constant]
+		type MaxNameLen: Get<u32>;
+
+		/// The `(pallet_name, function_name)` pairs allowed when the pallet is introduced.
+		type InitialWhitelist: Get<Vec<(&'static str, &'static str)>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// Whitelisted calls, by `(pallet_name, function_name)`.
+	#[pallet::storage]
+	pub type AllowedCalls<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, NameOf<T>, Blake2_128Concat, NameOf<T>, ()>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self {}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			Pallet::<T>::seed_initial_whitelist();
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= STORAGE_VERSION {
+				return 0
+			}
+
+			let count = Self::seed_initial_whitelist();
+			STORAGE_VERSION.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(1, count as Weight + 1)
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A call was added to the whitelist.
+		CallAllowed { pallet_name: NameOf<T>, function_name: NameOf<T> },
+		/// A call was removed from the whitelist.
+		CallDisallowed { pallet_name: NameOf<T>, function_name: NameOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A pallet or function name is longer than `MaxNameLen`.
+		NameTooLong,
+		/// The call is already whitelisted.
+		AlreadyAllowed,
+		/// The call is not whitelisted.
+		NotAllowed,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Allows contracts to dispatch `pallet_name::function_name`.
+		#[pallet::weight(T::WeightInfo::allow_call())]
+		pub fn allow_call(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			function_name: Vec<u8>,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			let (pallet_name, function_name) = Self::bounded_names(pallet_name, function_name)?;
+
+			ensure!(
+				!AllowedCalls::<T>::contains_key(&pallet_name, &function_name),
+				Error::<T>::AlreadyAllowed
+			);
+			AllowedCalls::<T>::insert(&pallet_name, &function_name, ());
+
+			Self::deposit_event(Event::CallAllowed { pallet_name, function_name });
+			Ok(())
+		}
+
+		/// Stops contracts from dispatching `pallet_name::function_name`.
+		#[pallet::weight(T::WeightInfo::disallow_call())]
+		pub fn disallow_call(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			function_name: Vec<u8>,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			let (pallet_name, function_name) = Self::bounded_names(pallet_name, function_name)?;
+
+			ensure!(
+				AllowedCalls::<T>::contains_key(&pallet_name, &function_name),
+				Error::<T>::NotAllowed
+			);
+			AllowedCalls::<T>::remove(&pallet_name, &function_name);
+
+			Self::deposit_event(Event::CallDisallowed { pallet_name, function_name });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns true if `pallet_name::function_name` is whitelisted.
+	pub fn is_allowed(pallet_name: &str, function_name: &str) -> bool {
+		match (
+			NameOf::<T>::try_from(pallet_name.as_bytes().to_vec()),
+			NameOf::<T>::try_from(function_name.as_bytes().to_vec()),
+		) {
+			(Ok(pallet_name), Ok(function_name)) =>
+				AllowedCalls::<T>::contains_key(pallet_name, function_name),
+			_ => false,
+		}
+	}
+
+	fn bounded_names(
+		pallet_name: Vec<u8>,
+		function_name: Vec<u8>,
+	) -> Result<(NameOf<T>, NameOf<T>), Error<T>> {
+		let pallet_name = pallet_name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+		let function_name = function_name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+		Ok((pallet_name, function_name))
+	}
+
+	/// Inserts `InitialWhitelist` and returns the number of entries written.
+	fn seed_initial_whitelist() -> u32 {
+		let mut count = 0;
+		for (pallet_name, function_name) in T::InitialWhitelist::get() {
+			let names = Self::bounded_names(
+				pallet_name.as_bytes().to_vec(),
+				function_name.as_bytes().to_vec(),
+			);
+			if let Ok((pallet_name, function_name)) = names {
+				AllowedCalls::<T>::insert(pallet_name, function_name, ());
+				count += 1;
+			}
+		}
+		count
+	}
+}
+
+/// Call filter letting through only whitelisted calls.
+pub struct Whitelist<T>(PhantomData<T>);
+
+impl<T: Config, Call: GetCallMetadata> Contains<Call> for Whitelist<T> {
+	fn contains(call: &Call) -> bool {
+		let metadata = call.get_call_metadata();
+		Pallet::<T>::is_allowed(metadata.pallet_name, metadata.function_name)
+	}
+}
@@ -0,0 +1,99 @@
+//! Unit tests for the Solar Call Filter pallet.
+
+use crate::{mock::*, AllowedCalls, Error, Event, Whitelist};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Contains, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_runtime::DispatchError;
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn set_storage() -> Call {
+	Call::System(frame_system::Call::set_storage { items: vec![] })
+}
+
+#[test]
+fn genesis_seeds_initial_whitelist() {
+	new_test_ext().execute_with(|| {
+		assert!(Whitelist::<Test>::contains(&remark()));
+		assert!(!Whitelist::<Test>::contains(&set_storage()));
+	});
+}
+
+#[test]
+fn allow_and_disallow_call_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CallFilter::allow_call(
+			Origin::root(),
+			b"System".to_vec(),
+			b"set_storage".to_vec()
+		));
+		System::assert_last_event(
+			Event::<Test>::CallAllowed {
+				pallet_name: b"System".to_vec().try_into().unwrap(),
+				function_name: b"set_storage".to_vec().try_into().unwrap(),
+			}
+			.into(),
+		);
+		assert!(Whitelist::<Test>::contains(&set_storage()));
+
+		assert_ok!(CallFilter::disallow_call(
+			Origin::root(),
+			b"System".to_vec(),
+			b"remark".to_vec()
+		));
+		assert!(!Whitelist::<Test>::contains(&remark()));
+	});
+}
+
+#[test]
+fn only_manager_can_change_whitelist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CallFilter::allow_call(Origin::signed(1), b"System".to_vec(), b"set_storage".to_vec()),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			CallFilter::disallow_call(Origin::signed(1), b"System".to_vec(), b"remark".to_vec()),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn invalid_changes_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CallFilter::allow_call(Origin::root(), b"System".to_vec(), b"remark".to_vec()),
+			Error::<Test>::AlreadyAllowed
+		);
+		assert_noop!(
+			CallFilter::disallow_call(Origin::root(), b"System".to_vec(), b"set_code".to_vec()),
+			Error::<Test>::NotAllowed
+		);
+		assert_noop!(
+			CallFilter::allow_call(Origin::root(), vec![b'a'; 33], b"remark".to_vec()),
+			Error::<Test>::NameTooLong
+		);
+	});
+}
+
+#[test]
+fn runtime_upgrade_seeds_whitelist_once() {
+	sp_io::TestExternalities::default().execute_with(|| {
+		assert_eq!(CallFilter::on_chain_storage_version(), StorageVersion::new(0));
+		assert!(!Whitelist::<Test>::contains(&remark()));
+
+		CallFilter::on_runtime_upgrade();
+		assert!(Whitelist::<Test>::contains(&remark()));
+		assert_eq!(CallFilter::on_chain_storage_version(), StorageVersion::new(1));
+
+		// A second upgrade must not re-add calls removed by governance.
+		AllowedCalls::<Test>::remove_all(None);
+		CallFilter::on_runtime_upgrade();
+		assert!(!Whitelist::<Test>::contains(&remark()));
+	});
+}
@@ -0,0 +1,49 @@
+//! Weights for pallet_solar_call_filter
+//!
+//! PLACEHOLDER, not benchmark output: hand-written weights, a flat cost plus the storage accesses
+//! of each extrinsic. `scripts/benchmark.sh` measures the benchmarks in `benchmarking.rs`; copy
+//! its output for this pallet here once it has run on the reference machine.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_solar_call_filter.
+pub trait WeightInfo {
+	fn allow_call() -> Weight;
+	fn disallow_call() -> Weight;
+}
+
+/// Placeholder weights for pallet_solar_call_filter, see the module docs.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: ContractsFilter AllowedCalls (r:1 w:1)
+	fn allow_call() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ContractsFilter AllowedCalls (r:1 w:1)
+	fn disallow_call() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn allow_call() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn disallow_call() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,95 @@
+//! Benchmarks for the Solar Market pallet.
+//!
+//! Tokens are minted through `pallet_solar_nft`, so these benchmarks assume it is `Config::Nfts`.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+/// Creates a collection with one token in it, owned by `owner`.
+fn create_token<T: Config + pallet_solar_nft::Config>(
+	owner: &T::AccountId,
+) -> (CollectionId, TokenId) {
+	let collection_id = pallet_solar_nft::Pallet::<T>::do_create_collection(
+		owner.clone(),
+		b"name".to_vec(),
+		b"symbol".to_vec(),
+	)
+	.unwrap();
+	pallet_solar_nft::Pallet::<T>::do_mint(owner.clone(), collection_id, 0, None).unwrap();
+	(collection_id, 0)
+}
+
+/// A price well above the existential deposit, so that both shares of it can be paid out.
+fn price<T: Config>() -> BalanceOf<T> {
+	T::Currency::minimum_balance().saturating_mul(100u32.into())
+}
+
+/// Offers a new token of `seller` at [`price`].
+fn create_offer<T: Config + pallet_solar_nft::Config>(
+	seller: &T::AccountId,
+) -> (CollectionId, TokenId) {
+	let (collection_id, token_id) = create_token::<T>(seller);
+	Pallet::<T>::offer(
+		RawOrigin::Signed(seller.clone()).into(),
+		collection_id,
+		token_id,
+		price::<T>(),
+	)
+	.unwrap();
+	(collection_id, token_id)
+}
+
+benchmarks! {
+	where_clause { where T: pallet_solar_nft::Config }
+
+	offer {
+		let caller: T::AccountId = whitelisted_caller();
+		let (collection_id, token_id) = create_token::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), collection_id, token_id, price::<T>())
+	verify {
+		assert_eq!(Listings::<T>::get(collection_id, token_id).map(|l| l.seller), Some(caller));
+	}
+
+	cancel_offer {
+		let caller: T::AccountId = whitelisted_caller();
+		let (collection_id, token_id) = create_offer::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), collection_id, token_id)
+	verify {
+		assert!(Listings::<T>::get(collection_id, token_id).is_none());
+	}
+
+	// The worst case pays a commission to the organiser.
+	buy {
+		let seller: T::AccountId = account("seller", 0, 0);
+		let (collection_id, token_id) = create_offer::<T>(&seller);
+		Organiser::<T>::put(account::<T::AccountId>("organiser", 0, 0));
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, price::<T>().saturating_mul(10u32.into()));
+	}: _(RawOrigin::Signed(caller.clone()), collection_id, token_id, price::<T>())
+	verify {
+		assert!(Listings::<T>::get(collection_id, token_id).is_none());
+		assert_eq!(T::Nfts::owner(&collection_id, &token_id), Some(caller));
+	}
+
+	set_organiser {
+		let origin = T::AdminOrigin::successful_origin();
+		let organiser: T::AccountId = account("organiser", 0, 0);
+	}: _<T::Origin>(origin, Some(organiser.clone()))
+	verify {
+		assert_eq!(Organiser::<T>::get(), Some(organiser));
+	}
+
+	set_trade_commission {
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, 50)
+	verify {
+		assert_eq!(TradeCommission::<T>::get(), 50);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,387 @@
+//! # Solar Market Pallet
+//!
+//! A native replacement for the ink! `market` and `SolarNFTMarket` contracts.
+//!
+//! A seller lists a token with a price through `offer`. The token is moved into the pallet's
+//! escrow account until it is sold or the offer is cancelled. `buy` settles in the native
+//! currency and splits the price between the seller and the organiser account using the
+//! commission rate (in percent) that was active when the offer was made, just like
+//! `SolarNFTMarket::trade_nft` does with `trade_commission`.
+//!
+//! Each listing records whether the creator of the token's collection, its owner in the NFT
+//! pallet, was a verified creator according to `Config::VerifiedCreators` when it was offered.
+//!
+//! Events keep the payloads of the contracts' `OfferCreated`, `OfferUpdated`, `OfferCancelled`
+//! and `Traded` events, in the field order of the `market` contract, so existing indexers keep
+//! working.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{
+		tokens::nonfungibles::{Inspect, Transfer},
+		Currency, ExistenceRequirement,
+	},
+	PalletId, RuntimeDebug,
+};
+pub use pallet_solar_nft::{CollectionId, TokenId};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating, Zero},
+	Perbill,
+};
+pub use weights::WeightInfo;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+pub type ListingOf<T> = Listing<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+/// A token offered for sale.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Listing<AccountId, Balance> {
+	/// The account that listed the token and receives the proceeds.
+	pub seller: AccountId,
+	/// The selling price.
+	pub price: Balance,
+	/// Commission rate in percent, paid to the organiser on trade.
+	pub commission_rate: u16,
+	/// Whether the creator of the token's collection was a verified creator when it was offered.
+	pub creator_verified: bool,
+}
+
+/// Tells whether an account is a verified creator.
+pub trait VerifiedCreators<AccountId> {
+	/// Whether `who` is a verified creator.
+	fn is_verified(who: &AccountId) -> bool;
+}
+
+/// No account is verified.
+impl<AccountId> VerifiedCreators<AccountId> for () {
+	fn is_verified(_: &AccountId) -> bool {
+		false
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, transactional};
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency trades are settled in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The NFTs that can be traded.
+		type Nfts: Transfer<Self::AccountId, ClassId = CollectionId, InstanceId = TokenId>;
+
+		/// Which accounts are verified creators.
+		type VerifiedCreators: VerifiedCreators<Self::AccountId>;
+
+		/// The ID of the escrow account holding listed tokens.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The origin allowed to change the organiser and the commission rate.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The current storage version.
+	///
+	/// Version 1 has no layout change: it records that [`migrations::v1::MoveOrganiser`] has run.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::type_value]
+	pub fn DefaultTradeCommission() -> u16 {
+		20
+	}
+
+	/// Who profits from trades.
+	#[pallet::storage]
+	#[pallet::getter(fn organiser)]
+	pub type Organiser<T: Config> = StorageValue<_, T::AccountId>;
+
+	/// Commission rate in percent applied to new offers.
+	#[pallet::storage]
+	#[pallet::getter(fn trade_commission)]
+	pub type TradeCommission<T> = StorageValue<_, u16, ValueQuery, DefaultTradeCommission>;
+
+	/// Current offers, by `(collection_id, token_id)`.
+	#[pallet::storage]
+	#[pallet::getter(fn listing)]
+	pub type Listings<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionId,
+		Blake2_128Concat,
+		TokenId,
+		ListingOf<T>,
+	>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Who profits from trades.
+		pub organiser: Option<T::AccountId>,
+		/// Commission rate in percent.
+		pub trade_commission: u16,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { organiser: None, trade_commission: DefaultTradeCommission::get() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			assert!(self.trade_commission <= 100, "trade commission is a percentage; qed");
+			if let Some(organiser) = &self.organiser {
+				Organiser::<T>::put(organiser);
+			}
+			TradeCommission::<T>::put(self.trade_commission);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A token was offered for sale.
+		OfferCreated {
+			seller: T::AccountId,
+			collection_id: CollectionId,
+			token_id: TokenId,
+			price: BalanceOf<T>,
+		},
+		/// The price of an offer was changed.
+		OfferUpdated {
+			seller: T::AccountId,
+			collection_id: CollectionId,
+			token_id: TokenId,
+			old_price: BalanceOf<T>,
+			new_price: BalanceOf<T>,
+		},
+		/// An offer was cancelled and the token returned to the seller.
+		OfferCancelled { seller: T::AccountId, collection_id: CollectionId, token_id: TokenId },
+		/// A token was sold.
+		Traded {
+			seller: T::AccountId,
+			buyer: T::AccountId,
+			collection_id: CollectionId,
+			token_id: TokenId,
+			price: BalanceOf<T>,
+			commission_rate: u16,
+			seller_income: BalanceOf<T>,
+			organiser_income: BalanceOf<T>,
+		},
+		/// The organiser account was changed.
+		OrganiserSet { organiser: Option<T::AccountId> },
+		/// The commission rate for new offers was changed.
+		TradeCommissionSet { commission_rate: u16 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The caller is not the owner of the token.
+		NotNFTOwner,
+		/// Token owner not found.
+		OwnerNotFound,
+		/// The token is not for sale.
+		NotForSale,
+		/// The caller is not the seller of the offer.
+		NotSeller,
+		/// Cannot buy a token from self.
+		CannotBuyOwnToken,
+		/// Purchasing price is less than selling price.
+		BidTooLow,
+		/// Commission rate must not exceed 100 percent.
+		InvalidCommission,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Offers a token for sale, or updates the price of an existing offer.
+		///
+		/// A new offer moves the token into escrow and records the current commission rate.
+		#[pallet::weight(T::WeightInfo::offer())]
+		pub fn offer(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			token_id: TokenId,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+
+			if let Some(mut listing) = Listings::<T>::get(collection_id, token_id) {
+				ensure!(listing.seller == seller, Error::<T>::NotSeller);
+				let old_price = listing.price;
+				listing.price = price;
+				Listings::<T>::insert(collection_id, token_id, listing);
+
+				Self::deposit_event(Event::OfferUpdated {
+					seller,
+					collection_id,
+					token_id,
+					old_price,
+					new_price: price,
+				});
+				return Ok(())
+			}
+
+			let owner =
+				T::Nfts::owner(&collection_id, &token_id).ok_or(Error::<T>::OwnerNotFound)?;
+			ensure!(owner == seller, Error::<T>::NotNFTOwner);
+
+			T::Nfts::transfer(&collection_id, &token_id, &Self::account_id())?;
+			Listings::<T>::insert(
+				collection_id,
+				token_id,
+				Listing {
+					seller: seller.clone(),
+					price,
+					commission_rate: Self::trade_commission(),
+					creator_verified: Self::is_creator_verified(&collection_id),
+				},
+			);
+
+			Self::deposit_event(Event::OfferCreated { seller, collection_id, token_id, price });
+			Ok(())
+		}
+
+		/// Cancels an offer and returns the token to the seller.
+		#[pallet::weight(T::WeightInfo::cancel_offer())]
+		pub fn cancel_offer(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			token_id: TokenId,
+		) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+
+			let listing =
+				Listings::<T>::get(collection_id, token_id).ok_or(Error::<T>::NotForSale)?;
+			ensure!(listing.seller == seller, Error::<T>::NotSeller);
+
+			T::Nfts::transfer(&collection_id, &token_id, &seller)?;
+			Listings::<T>::remove(collection_id, token_id);
+
+			Self::deposit_event(Event::OfferCancelled { seller, collection_id, token_id });
+			Ok(())
+		}
+
+		/// Buys an offered token, paying at most `bid`.
+		///
+		/// The seller receives the price minus the commission, which goes to the organiser.
+		#[pallet::weight(T::WeightInfo::buy())]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			token_id: TokenId,
+			bid: BalanceOf<T>,
+		) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+
+			let Listing { seller, price, commission_rate, .. } =
+				Listings::<T>::get(collection_id, token_id).ok_or(Error::<T>::NotForSale)?;
+			ensure!(seller != buyer, Error::<T>::CannotBuyOwnToken);
+			ensure!(price <= bid, Error::<T>::BidTooLow);
+
+			let (organiser_income, seller_income) = match Self::organiser() {
+				Some(organiser) => {
+					let commission = Perbill::from_percent(commission_rate.into()) * price;
+					T::Currency::transfer(
+						&buyer,
+						&organiser,
+						commission,
+						ExistenceRequirement::KeepAlive,
+					)?;
+					(commission, price.saturating_sub(commission))
+				},
+				None => (Zero::zero(), price),
+			};
+			T::Currency::transfer(&buyer, &seller, seller_income, ExistenceRequirement::KeepAlive)?;
+
+			T::Nfts::transfer(&collection_id, &token_id, &buyer)?;
+			Listings::<T>::remove(collection_id, token_id);
+
+			Self::deposit_event(Event::Traded {
+				seller,
+				buyer,
+				collection_id,
+				token_id,
+				price,
+				commission_rate,
+				seller_income,
+				organiser_income,
+			});
+			Ok(())
+		}
+
+		/// Sets the account that receives trade commissions.
+		#[pallet::weight(T::WeightInfo::set_organiser())]
+		pub fn set_organiser(
+			origin: OriginFor<T>,
+			organiser: Option<T::AccountId>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Organiser::<T>::set(organiser.clone());
+
+			Self::deposit_event(Event::OrganiserSet { organiser });
+			Ok(())
+		}
+
+		/// Sets the commission rate, in percent, for new offers.
+		#[pallet::weight(T::WeightInfo::set_trade_commission())]
+		pub fn set_trade_commission(origin: OriginFor<T>, commission_rate: u16) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(commission_rate <= 100, Error::<T>::InvalidCommission);
+
+			TradeCommission::<T>::put(commission_rate);
+
+			Self::deposit_event(Event::TradeCommissionSet { commission_rate });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The escrow account holding listed tokens.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// Whether the owner of `collection_id` is a verified creator.
+	pub fn is_creator_verified(collection_id: &CollectionId) -> bool {
+		T::Nfts::class_owner(collection_id)
+			.map_or(false, |creator| T::VerifiedCreators::is_verified(&creator))
+	}
+}
@@ -0,0 +1,33 @@
+//! Storage migrations of the market pallet.
+
+/// Version 1.
+pub mod v1 {
+	use crate::{Config, Organiser, Pallet};
+	use frame_support::{
+		traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		weights::Weight,
+	};
+	use sp_std::marker::PhantomData;
+
+	/// Moves `Organiser` to `NewOrganiser` on chains started before the runtime set an organiser
+	/// at genesis.
+	///
+	/// Runs once, gated on storage version 1 of the pallet, so an organiser chosen later through
+	/// governance is left alone.
+	pub struct MoveOrganiser<T, NewOrganiser>(PhantomData<(T, NewOrganiser)>);
+
+	impl<T: Config, NewOrganiser: Get<T::AccountId>> OnRuntimeUpgrade
+		for MoveOrganiser<T, NewOrganiser>
+	{
+		fn on_runtime_upgrade() -> Weight {
+			let version = StorageVersion::new(1);
+			if Pallet::<T>::on_chain_storage_version() >= version {
+				return T::DbWeight::get().reads(1)
+			}
+
+			Organiser::<T>::put(NewOrganiser::get());
+			version.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+	}
+}
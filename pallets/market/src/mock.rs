@@ -0,0 +1,132 @@
+//! Test environment for the Solar Market pallet.
+
+use super::*;
+use crate as pallet_solar_market;
+
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ConstU32, ConstU64, GenesisBuild},
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Nft: pallet_solar_nft::{Pallet, Call, Storage, Event<T>, Config<T>},
+		Market: pallet_solar_market::{Pallet, Call, Storage, Event<T>, Config<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+
+impl pallet_solar_nft::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type CollectionDeposit = ConstU64<10>;
+	type ItemDeposit = ConstU64<1>;
+	type StringLimit = ConstU32<50>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MarketPalletId: PalletId = PalletId(*b"sol/mrkt");
+}
+
+/// Only `ALICE` is a verified creator.
+pub struct VerifiedAlice;
+
+impl VerifiedCreators<u64> for VerifiedAlice {
+	fn is_verified(who: &u64) -> bool {
+		*who == ALICE
+	}
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type Nfts = Nft;
+	type VerifiedCreators = VerifiedAlice;
+	type PalletId = MarketPalletId;
+	type AdminOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+pub const ORGANISER: u64 = 4;
+
+/// Builds genesis storage where `ALICE`, a verified creator, owns collection 0 and `BOB` owns
+/// tokens 1 and 2 in it. `BOB` and `CHARLIE` are endowed with 1000 units, `ORGANISER` receives
+/// commissions at 20%.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(BOB, 1000), (CHARLIE, 1000), (ORGANISER, 1)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_solar_nft::GenesisConfig::<Test> {
+		collections: vec![(ALICE, b"hello".to_vec(), b"world".to_vec())],
+		tokens: vec![(0, 1, BOB, None), (0, 2, BOB, None)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_solar_market::GenesisConfig::<Test> { organiser: Some(ORGANISER), trade_commission: 20 }
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,191 @@
+//! Unit tests for the Solar Market pallet.
+
+use crate::{mock::*, Error, Event, Listing};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{ConstU64, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_runtime::DispatchError;
+
+#[test]
+fn offer_escrows_token() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Market::offer(Origin::signed(BOB), 0, 1, 100));
+		System::assert_last_event(
+			Event::<Test>::OfferCreated { seller: BOB, collection_id: 0, token_id: 1, price: 100 }
+				.into(),
+		);
+
+		assert_eq!(Nft::owner_of(0, 1), Some(Market::account_id()));
+		assert_eq!(
+			Market::listing(0, 1),
+			Some(Listing { seller: BOB, price: 100, commission_rate: 20, creator_verified: true })
+		);
+	});
+}
+
+#[test]
+fn offer_records_whether_the_creator_is_verified() {
+	new_test_ext().execute_with(|| {
+		let collection_id =
+			Nft::do_create_collection(CHARLIE, b"a".to_vec(), b"b".to_vec()).unwrap();
+		assert_ok!(Nft::do_mint(CHARLIE, collection_id, 0, None));
+
+		assert_ok!(Market::offer(Origin::signed(CHARLIE), collection_id, 0, 100));
+		assert!(!Market::listing(collection_id, 0).unwrap().creator_verified);
+	});
+}
+
+#[test]
+fn offer_requires_token_owner() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Market::offer(Origin::signed(CHARLIE), 0, 1, 100), Error::<Test>::NotNFTOwner);
+		assert_noop!(Market::offer(Origin::signed(BOB), 0, 9, 100), Error::<Test>::OwnerNotFound);
+	});
+}
+
+#[test]
+fn offer_again_updates_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Market::offer(Origin::signed(BOB), 0, 1, 100));
+		assert_noop!(Market::offer(Origin::signed(CHARLIE), 0, 1, 50), Error::<Test>::NotSeller);
+
+		assert_ok!(Market::offer(Origin::signed(BOB), 0, 1, 150));
+		System::assert_last_event(
+			Event::<Test>::OfferUpdated {
+				seller: BOB,
+				collection_id: 0,
+				token_id: 1,
+				old_price: 100,
+				new_price: 150,
+			}
+			.into(),
+		);
+		assert_eq!(Market::listing(0, 1).unwrap().price, 150);
+	});
+}
+
+#[test]
+fn cancel_offer_returns_token() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Market::offer(Origin::signed(BOB), 0, 1, 100));
+		assert_noop!(Market::cancel_offer(Origin::signed(CHARLIE), 0, 1), Error::<Test>::NotSeller);
+
+		assert_ok!(Market::cancel_offer(Origin::signed(BOB), 0, 1));
+		System::assert_last_event(
+			Event::<Test>::OfferCancelled { seller: BOB, collection_id: 0, token_id: 1 }.into(),
+		);
+		assert_eq!(Nft::owner_of(0, 1), Some(BOB));
+		assert_eq!(Market::listing(0, 1), None);
+		assert_noop!(Market::cancel_offer(Origin::signed(BOB), 0, 1), Error::<Test>::NotForSale);
+	});
+}
+
+#[test]
+fn buy_splits_price_between_seller_and_organiser() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Market::offer(Origin::signed(BOB), 0, 1, 100));
+		assert_ok!(Market::buy(Origin::signed(CHARLIE), 0, 1, 120));
+		System::assert_last_event(
+			Event::<Test>::Traded {
+				seller: BOB,
+				buyer: CHARLIE,
+				collection_id: 0,
+				token_id: 1,
+				price: 100,
+				commission_rate: 20,
+				seller_income: 80,
+				organiser_income: 20,
+			}
+			.into(),
+		);
+
+		assert_eq!(Nft::owner_of(0, 1), Some(CHARLIE));
+		assert_eq!(Market::listing(0, 1), None);
+		assert_eq!(Balances::free_balance(CHARLIE), 900);
+		assert_eq!(Balances::free_balance(BOB), 1080);
+		assert_eq!(Balances::free_balance(ORGANISER), 21);
+	});
+}
+
+#[test]
+fn buy_without_organiser_pays_seller_in_full() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Market::set_organiser(Origin::root(), None));
+		assert_ok!(Market::offer(Origin::signed(BOB), 0, 1, 100));
+		assert_ok!(Market::buy(Origin::signed(CHARLIE), 0, 1, 100));
+
+		assert_eq!(Balances::free_balance(BOB), 1100);
+		assert_eq!(Balances::free_balance(ORGANISER), 1);
+	});
+}
+
+#[test]
+fn buy_checks_offer_and_bid() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Market::buy(Origin::signed(CHARLIE), 0, 1, 100), Error::<Test>::NotForSale);
+
+		assert_ok!(Market::offer(Origin::signed(BOB), 0, 1, 100));
+		assert_noop!(Market::buy(Origin::signed(BOB), 0, 1, 100), Error::<Test>::CannotBuyOwnToken);
+		assert_noop!(Market::buy(Origin::signed(CHARLIE), 0, 1, 99), Error::<Test>::BidTooLow);
+	});
+}
+
+#[test]
+fn buy_with_insufficient_balance_fails_atomically() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Market::offer(Origin::signed(BOB), 0, 1, 2000));
+		assert!(Market::buy(Origin::signed(CHARLIE), 0, 1, 2000).is_err());
+
+		assert_eq!(Balances::free_balance(CHARLIE), 1000);
+		assert_eq!(Balances::free_balance(ORGANISER), 1);
+		assert_eq!(Nft::owner_of(0, 1), Some(Market::account_id()));
+	});
+}
+
+#[test]
+fn commission_is_recorded_per_offer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Market::offer(Origin::signed(BOB), 0, 1, 100));
+		assert_ok!(Market::set_trade_commission(Origin::root(), 50));
+		assert_ok!(Market::offer(Origin::signed(BOB), 0, 2, 100));
+
+		assert_eq!(Market::listing(0, 1).unwrap().commission_rate, 20);
+		assert_eq!(Market::listing(0, 2).unwrap().commission_rate, 50);
+	});
+}
+
+#[test]
+fn admin_calls_require_admin_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Market::set_organiser(Origin::signed(BOB), Some(BOB)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Market::set_trade_commission(Origin::signed(BOB), 10),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Market::set_trade_commission(Origin::root(), 101),
+			Error::<Test>::InvalidCommission
+		);
+	});
+}
+
+#[test]
+fn move_organiser_runs_once() {
+	new_test_ext().execute_with(|| {
+		type MoveOrganiser = crate::migrations::v1::MoveOrganiser<Test, ConstU64<CHARLIE>>;
+
+		assert_eq!(Market::on_chain_storage_version(), StorageVersion::new(0));
+		MoveOrganiser::on_runtime_upgrade();
+		assert_eq!(Market::organiser(), Some(CHARLIE));
+		assert_eq!(Market::on_chain_storage_version(), StorageVersion::new(1));
+
+		// An organiser chosen later through governance survives further upgrades.
+		assert_ok!(Market::set_organiser(Origin::root(), Some(ORGANISER)));
+		MoveOrganiser::on_runtime_upgrade();
+		assert_eq!(Market::organiser(), Some(ORGANISER));
+	});
+}
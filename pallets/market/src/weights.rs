@@ -0,0 +1,99 @@
+//! Weights for pallet_solar_market
+//!
+//! PLACEHOLDER, not benchmark output: hand-written weights, a flat cost plus the storage accesses
+//! of each extrinsic. `scripts/benchmark.sh` measures the benchmarks in `benchmarking.rs`; copy
+//! its output for this pallet here once it has run on the reference machine.
+//!
+//! The flat costs of calls any signed account can make are set several times above what
+//! Substrate measured for comparable calls, such as `pallet_uniques::mint`, so that they are not
+//! underpriced until then.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_solar_market.
+pub trait WeightInfo {
+	fn offer() -> Weight;
+	fn cancel_offer() -> Weight;
+	fn buy() -> Weight;
+	fn set_organiser() -> Weight;
+	fn set_trade_commission() -> Weight;
+}
+
+/// Placeholder weights for pallet_solar_market, see the module docs.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Market Listings (r:1 w:1)
+	// Storage: Market TradeCommission (r:1 w:0)
+	// Storage: Nft Collections (r:1 w:0)
+	// Storage: Identity IdentityOf (r:1 w:0)
+	// Storage: Nft Tokens (r:1 w:1)
+	// Storage: Nft OwnedTokensCount (r:2 w:2)
+	// Storage: Nft OwnedTokens (r:0 w:2)
+	fn offer() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Market Listings (r:1 w:1)
+	// Storage: Nft Tokens (r:1 w:1)
+	// Storage: Nft OwnedTokensCount (r:2 w:2)
+	// Storage: Nft OwnedTokens (r:0 w:2)
+	fn cancel_offer() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Market Listings (r:1 w:1)
+	// Storage: Market Organiser (r:1 w:0)
+	// Storage: System Account (r:3 w:3)
+	// Storage: Nft Tokens (r:1 w:1)
+	// Storage: Nft OwnedTokensCount (r:2 w:2)
+	// Storage: Nft OwnedTokens (r:0 w:2)
+	fn buy() -> Weight {
+		(300_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: Market Organiser (r:0 w:1)
+	fn set_organiser() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Market TradeCommission (r:0 w:1)
+	fn set_trade_commission() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn offer() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn cancel_offer() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn buy() -> Weight {
+		(300_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn set_organiser() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_trade_commission() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
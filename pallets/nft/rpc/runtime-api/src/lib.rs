@@ -0,0 +1,41 @@
+//! Runtime API definition for the NFT RPC.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+pub use pallet_solar_nft::{CollectionId, CollectionInfo, TokenId, TokenInfo, MAX_TOKENS_PER_PAGE};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// The API to query NFT collections and tokens.
+	///
+	/// Names, symbols and metadata are returned as plain byte strings.
+	///
+	/// Version 2 returns the tokens of an account one page at a time.
+	#[api_version(2)]
+	pub trait NftApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// The owner of a token.
+		fn owner_of(collection_id: CollectionId, token_id: TokenId) -> Option<AccountId>;
+
+		/// All tokens owned by `account`, as `(collection_id, token_id)`.
+		#[changed_in(2)]
+		fn tokens_of(account: AccountId) -> Vec<(CollectionId, TokenId)>;
+
+		/// The tokens owned by `account`, as `(collection_id, token_id)`, skipping the first
+		/// `start` and returning at most `limit`, capped at `MAX_TOKENS_PER_PAGE`.
+		fn tokens_of(account: AccountId, start: u32, limit: u32) -> Vec<(CollectionId, TokenId)>;
+
+		/// Details of a token.
+		fn token_info(
+			collection_id: CollectionId,
+			token_id: TokenId,
+		) -> Option<TokenInfo<AccountId, Vec<u8>>>;
+
+		/// Details of a collection.
+		fn collection_info(
+			collection_id: CollectionId,
+		) -> Option<CollectionInfo<AccountId, Vec<u8>>>;
+	}
+}
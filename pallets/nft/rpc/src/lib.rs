@@ -0,0 +1,234 @@
+//! RPC interface for the NFT pallet.
+//!
+//! Answers the questions the `nft` contract answered with messages, such as who owns a token,
+//! without hand-encoded selectors or SCALE decoding. Names, symbols and metadata are returned as
+//! strings, with invalid UTF-8 replaced by `U+FFFD`.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+pub use pallet_solar_nft_rpc_runtime_api::NftApi as NftRuntimeApi;
+use pallet_solar_nft_rpc_runtime_api::{CollectionId, TokenId, MAX_TOKENS_PER_PAGE};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// A token, addressed by its collection and its ID within the collection.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Token {
+	pub collection_id: CollectionId,
+	pub token_id: TokenId,
+}
+
+/// Details of a token.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenDetails<AccountId> {
+	pub collection_id: CollectionId,
+	pub token_id: TokenId,
+	/// The owner of the token.
+	pub owner: AccountId,
+	/// The account allowed to transfer the token on behalf of the owner.
+	pub approval: Option<AccountId>,
+	/// The URI of the token.
+	pub metadata: Option<String>,
+}
+
+/// Details of a collection.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionDetails<AccountId> {
+	pub collection_id: CollectionId,
+	/// The account allowed to mint tokens into the collection.
+	pub owner: AccountId,
+	pub name: String,
+	pub symbol: String,
+	/// Number of tokens currently existing in the collection.
+	pub total_supply: u64,
+}
+
+#[rpc(client, server, namespace = "solar_nft")]
+pub trait NftApi<BlockHash, AccountId> {
+	/// The owner of a token, `null` if it does not exist.
+	#[method(name = "ownerOf")]
+	fn owner_of(
+		&self,
+		collection_id: CollectionId,
+		token_id: TokenId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<AccountId>>;
+
+	/// The tokens owned by `account`, like `all_token_by_account` of the `nft` contract but one
+	/// page at a time: the first `start` tokens are skipped (default 0) and at most `limit` are
+	/// returned (default and maximum `MAX_TOKENS_PER_PAGE`).
+	#[method(name = "tokensOf")]
+	fn tokens_of(
+		&self,
+		account: AccountId,
+		start: Option<u32>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Token>>;
+
+	/// Details of a token, `null` if it does not exist.
+	#[method(name = "tokenInfo")]
+	fn token_info(
+		&self,
+		collection_id: CollectionId,
+		token_id: TokenId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<TokenDetails<AccountId>>>;
+
+	/// Details of a collection, `null` if it does not exist.
+	#[method(name = "collectionInfo")]
+	fn collection_info(
+		&self,
+		collection_id: CollectionId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<CollectionDetails<AccountId>>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// Provides RPC methods to query NFT collections and tokens.
+pub struct Nft<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> Nft<C, B> {
+	/// Creates a new instance of the NFT RPC.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Display, message: &str) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		message.to_string(),
+		Some(e.to_string()),
+	))
+	.into()
+}
+
+fn into_string(bytes: Vec<u8>) -> String {
+	String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[async_trait]
+impl<C, Block, AccountId> NftApiServer<<Block as BlockT>::Hash, AccountId> for Nft<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: NftRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn owner_of(
+		&self,
+		collection_id: CollectionId,
+		token_id: TokenId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.owner_of(&at, collection_id, token_id)
+			.map_err(|e| runtime_error(e, "Unable to query the token owner."))
+	}
+
+	fn tokens_of(
+		&self,
+		account: AccountId,
+		start: Option<u32>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<Token>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let (start, limit) = (start.unwrap_or(0), limit.unwrap_or(MAX_TOKENS_PER_PAGE));
+		let version = api
+			.api_version::<dyn NftRuntimeApi<Block, AccountId>>(&at)
+			.map_err(|e| runtime_error(e, "Unable to query the NFT runtime API version."))?;
+		let tokens = if version.map_or(true, |version| version >= 2) {
+			api.tokens_of(&at, account, start, limit)
+		} else {
+			// Runtimes before version 2 return all tokens, page them here.
+			#[allow(deprecated)]
+			let all = api.tokens_of_before_version_2(&at, account);
+			all.map(|tokens| {
+				tokens
+					.into_iter()
+					.skip(start as usize)
+					.take(limit.min(MAX_TOKENS_PER_PAGE) as usize)
+					.collect()
+			})
+		}
+		.map_err(|e| runtime_error(e, "Unable to query the tokens of the account."))?;
+		Ok(tokens
+			.into_iter()
+			.map(|(collection_id, token_id)| Token { collection_id, token_id })
+			.collect())
+	}
+
+	fn token_info(
+		&self,
+		collection_id: CollectionId,
+		token_id: TokenId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<TokenDetails<AccountId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let token = api
+			.token_info(&at, collection_id, token_id)
+			.map_err(|e| runtime_error(e, "Unable to query the token."))?;
+		Ok(token.map(|token| TokenDetails {
+			collection_id,
+			token_id,
+			owner: token.owner,
+			approval: token.approval,
+			metadata: token.metadata.map(into_string),
+		}))
+	}
+
+	fn collection_info(
+		&self,
+		collection_id: CollectionId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<CollectionDetails<AccountId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let collection = api
+			.collection_info(&at, collection_id)
+			.map_err(|e| runtime_error(e, "Unable to query the collection."))?;
+		Ok(collection.map(|collection| CollectionDetails {
+			collection_id,
+			owner: collection.owner,
+			name: into_string(collection.name),
+			symbol: into_string(collection.symbol),
+			total_supply: collection.total_supply,
+		}))
+	}
+}
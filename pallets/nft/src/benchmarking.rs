@@ -0,0 +1,97 @@
+//! Benchmarks for the Solar NFT pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Saturating, StaticLookup};
+
+/// A string of the maximum length.
+fn max_string<T: Config>() -> Vec<u8> {
+	vec![b'x'; T::StringLimit::get() as usize]
+}
+
+/// Endows `who` with enough to reserve a collection deposit and some item deposits.
+fn fund<T: Config>(who: &T::AccountId) {
+	let amount = T::CollectionDeposit::get()
+		.saturating_add(T::ItemDeposit::get())
+		.saturating_add(T::Currency::minimum_balance())
+		.saturating_mul(10u32.into());
+	T::Currency::make_free_balance_be(who, amount);
+}
+
+/// Creates a collection owned by `owner` with one token in it, also owned by `owner`. Deposits
+/// are reserved like for any other caller.
+fn create_token<T: Config>(owner: &T::AccountId) -> (CollectionId, TokenId) {
+	fund::<T>(owner);
+	let collection_id = NextCollectionId::<T>::get();
+	let origin: T::Origin = RawOrigin::Signed(owner.clone()).into();
+	Pallet::<T>::create_collection(origin.clone(), max_string::<T>(), max_string::<T>()).unwrap();
+	let owner_lookup = T::Lookup::unlookup(owner.clone());
+	Pallet::<T>::mint(origin, owner_lookup, collection_id, 0, Some(max_string::<T>())).unwrap();
+	(collection_id, 0)
+}
+
+benchmarks! {
+	create_collection {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let collection_id = NextCollectionId::<T>::get();
+	}: _(RawOrigin::Signed(caller.clone()), max_string::<T>(), max_string::<T>())
+	verify {
+		assert_eq!(Collections::<T>::get(collection_id).map(|c| c.owner), Some(caller));
+	}
+
+	mint {
+		let caller: T::AccountId = whitelisted_caller();
+		let (collection_id, _) = create_token::<T>(&caller);
+		let to: T::AccountId = account("to", 0, 0);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+	}: _(RawOrigin::Signed(caller), to_lookup, collection_id, 1, Some(max_string::<T>()))
+	verify {
+		assert_eq!(Pallet::<T>::owner_of(collection_id, 1), Some(to));
+	}
+
+	burn {
+		let caller: T::AccountId = whitelisted_caller();
+		let (collection_id, token_id) = create_token::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), collection_id, token_id)
+	verify {
+		assert_eq!(Pallet::<T>::owner_of(collection_id, token_id), None);
+		assert!(TokenDeposits::<T>::get(collection_id, token_id).is_none());
+	}
+
+	transfer {
+		let caller: T::AccountId = whitelisted_caller();
+		let (collection_id, token_id) = create_token::<T>(&caller);
+		let dest: T::AccountId = account("dest", 0, 0);
+		let dest_lookup = T::Lookup::unlookup(dest.clone());
+	}: _(RawOrigin::Signed(caller), dest_lookup, collection_id, token_id)
+	verify {
+		assert_eq!(Pallet::<T>::owner_of(collection_id, token_id), Some(dest));
+	}
+
+	approve {
+		let caller: T::AccountId = whitelisted_caller();
+		let (collection_id, token_id) = create_token::<T>(&caller);
+		let to: T::AccountId = account("to", 0, 0);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+	}: _(RawOrigin::Signed(caller), Some(to_lookup), collection_id, token_id)
+	verify {
+		let token = Tokens::<T>::get(collection_id, token_id).unwrap();
+		assert_eq!(token.approval, Some(to));
+	}
+
+	set_metadata {
+		let caller: T::AccountId = whitelisted_caller();
+		let (collection_id, token_id) = create_token::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), collection_id, token_id, max_string::<T>())
+	verify {
+		let token = Tokens::<T>::get(collection_id, token_id).unwrap();
+		assert_eq!(token.metadata.map(|m| m.into_inner()), Some(max_string::<T>()));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,571 @@
+//! # Solar NFT Pallet
+//!
+//! A native replacement for the ink! `nft` contract. Tokens are grouped into collections and
+//! addressed by `(CollectionId, TokenId)`, exactly like `token_collection` in the contract, so
+//! the runtime can reason about ownership and transfers do not pay contract gas.
+//!
+//! The owner of a collection takes the role of the contract owner: only it can mint tokens
+//! into the collection. Everything else follows the contract's messages:
+//!
+//! - `mint` fails with `TokenExists` if `(collection_id, token_id)` is taken.
+//! - `burn` is only allowed for the token owner.
+//! - `transfer`/`transfer_from` are allowed for the owner or the approved account, and clear the
+//!   approval.
+//! - `approve` is only allowed for the token owner, `None` clears the approval.
+//! - `set_metadata` is allowed for the owner or the approved account.
+//!
+//! Unlike the contract, storage is paid for with deposits: `create_collection` reserves
+//! `CollectionDeposit` from the collection owner and `mint` reserves `ItemDeposit` from it for
+//! every token. The item deposit is returned to the collection owner when the token is burned.
+//! Collections cannot be destroyed yet, so their deposit stays reserved. Collections and tokens
+//! created at genesis hold no deposit.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	ensure,
+	traits::{
+		tokens::nonfungibles::{Inspect, Transfer},
+		Currency, ReservableCurrency,
+	},
+	BoundedVec, RuntimeDebug,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{DispatchError, DispatchResult};
+use sp_std::prelude::*;
+pub use weights::WeightInfo;
+
+/// A collection ID.
+pub type CollectionId = u64;
+
+/// A token ID.
+pub type TokenId = u64;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The maximum number of tokens returned by one call of [`Pallet::tokens_of`].
+pub const MAX_TOKENS_PER_PAGE: u32 = 1_000;
+
+/// Bounded byte string used for names, symbols and token metadata.
+pub type BoundedStringOf<T> = BoundedVec<u8, <T as Config>::StringLimit>;
+
+pub type CollectionInfoOf<T> =
+	CollectionInfo<<T as frame_system::Config>::AccountId, BoundedStringOf<T>>;
+
+pub type TokenInfoOf<T> = TokenInfo<<T as frame_system::Config>::AccountId, BoundedStringOf<T>>;
+
+/// Information about a collection.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CollectionInfo<AccountId, BoundedString> {
+	/// The account allowed to mint tokens into this collection.
+	pub owner: AccountId,
+	/// Name of the collection.
+	pub name: BoundedString,
+	/// Symbol of the collection, usually a shorter version of the name.
+	pub symbol: BoundedString,
+	/// Number of tokens currently existing in this collection.
+	pub total_supply: u64,
+}
+
+/// Information about a token, such as owner, approval, etc.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct TokenInfo<AccountId, BoundedString> {
+	/// The owner of the token.
+	pub owner: AccountId,
+	/// The account allowed to transfer the token on behalf of the owner.
+	pub approval: Option<AccountId>,
+	/// The Uniform Resource Identifier (URI) of the token.
+	pub metadata: Option<BoundedString>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, transactional};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::StaticLookup;
+
+	type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency collection and item deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved from the owner when creating a collection.
+		#[pallet::constant]
+		type CollectionDeposit: Get<BalanceOf<Self>>;
+
+		/// The deposit reserved from the collection owner for every minted token.
+		#[pallet::constant]
+		type ItemDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum length of a collection name, symbol or token metadata.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The collection ID handed out to the next created collection.
+	#[pallet::storage]
+	#[pallet::getter(fn next_collection_id)]
+	pub type NextCollectionId<T> = StorageValue<_, CollectionId, ValueQuery>;
+
+	/// Details of all collections.
+	#[pallet::storage]
+	#[pallet::getter(fn collection)]
+	pub type Collections<T: Config> =
+		StorageMap<_, Blake2_128Concat, CollectionId, CollectionInfoOf<T>>;
+
+	/// Mapping from `(collection_id, token_id)` to token info.
+	#[pallet::storage]
+	#[pallet::getter(fn token)]
+	pub type Tokens<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionId,
+		Blake2_128Concat,
+		TokenId,
+		TokenInfoOf<T>,
+	>;
+
+	/// The item deposit of a token, as `(depositor, amount)`. Tokens minted at genesis have none.
+	#[pallet::storage]
+	pub type TokenDeposits<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionId,
+		Blake2_128Concat,
+		TokenId,
+		(T::AccountId, BalanceOf<T>),
+	>;
+
+	/// Mapping from owner to the tokens it owns.
+	#[pallet::storage]
+	pub type OwnedTokens<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(CollectionId, TokenId),
+		(),
+	>;
+
+	/// Mapping from owner to number of owned tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn balance_of)]
+	pub type OwnedTokensCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Collections to create, as `(owner, name, symbol)`. IDs are assigned in order from 0.
+		pub collections: Vec<(T::AccountId, Vec<u8>, Vec<u8>)>,
+		/// Tokens to mint, as `(collection_id, token_id, owner, metadata)`.
+		pub tokens: Vec<(CollectionId, TokenId, T::AccountId, Option<Vec<u8>>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { collections: Vec::new(), tokens: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (owner, name, symbol) in &self.collections {
+				Pallet::<T>::do_create_collection(owner.clone(), name.clone(), symbol.clone())
+					.expect("genesis collections must be valid; qed");
+			}
+			for (collection_id, token_id, owner, metadata) in &self.tokens {
+				Pallet::<T>::do_mint(owner.clone(), *collection_id, *token_id, metadata.clone())
+					.expect("genesis tokens must be valid; qed");
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A collection was created.
+		CollectionCreated { collection_id: CollectionId, owner: T::AccountId },
+		/// A token was minted.
+		Minted { owner: T::AccountId, collection_id: CollectionId, token_id: TokenId },
+		/// A token was burned.
+		Burned { caller: T::AccountId, collection_id: CollectionId, token_id: TokenId },
+		/// A token was transferred.
+		Transfer {
+			from: T::AccountId,
+			to: T::AccountId,
+			collection_id: CollectionId,
+			token_id: TokenId,
+		},
+		/// The approval of a token was set or cleared.
+		Approval {
+			from: T::AccountId,
+			to: Option<T::AccountId>,
+			collection_id: CollectionId,
+			token_id: TokenId,
+		},
+		/// The metadata of a token was set.
+		MetadataSet { collection_id: CollectionId, token_id: TokenId, metadata: BoundedStringOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The caller is not the owner of the token or collection.
+		NotOwner,
+		/// The caller is neither the owner nor the approved account of the token.
+		NotApproved,
+		/// The caller is not allowed to perform this operation.
+		NotAllowed,
+		/// The token already exists.
+		TokenExists,
+		/// The token does not exist.
+		TokenNotFound,
+		/// The collection does not exist.
+		CollectionNotFound,
+		/// A name, symbol or metadata is longer than `StringLimit`.
+		StringTooLong,
+		/// No more collection IDs are available.
+		NoAvailableCollectionId,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Creates a new collection owned by the caller and reserves `CollectionDeposit`.
+		#[pallet::weight(T::WeightInfo::create_collection())]
+		#[transactional]
+		pub fn create_collection(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			T::Currency::reserve(&owner, T::CollectionDeposit::get())?;
+			Self::do_create_collection(owner, name, symbol)?;
+			Ok(())
+		}
+
+		/// Creates a new token. Only the collection owner can mint, and `ItemDeposit` is
+		/// reserved from it.
+		#[pallet::weight(T::WeightInfo::mint())]
+		#[transactional]
+		pub fn mint(
+			origin: OriginFor<T>,
+			to: AccountIdLookupOf<T>,
+			collection_id: CollectionId,
+			token_id: TokenId,
+			metadata: Option<Vec<u8>>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+
+			ensure!(!Tokens::<T>::contains_key(collection_id, token_id), Error::<T>::TokenExists);
+			let collection =
+				Collections::<T>::get(collection_id).ok_or(Error::<T>::CollectionNotFound)?;
+			ensure!(collection.owner == caller, Error::<T>::NotOwner);
+
+			let deposit = T::ItemDeposit::get();
+			T::Currency::reserve(&caller, deposit)?;
+			Self::do_mint(to, collection_id, token_id, metadata)?;
+			TokenDeposits::<T>::insert(collection_id, token_id, (caller, deposit));
+			Ok(())
+		}
+
+		/// Deletes an existing token and returns its deposit to the collection owner. Only the
+		/// owner can burn the token.
+		#[pallet::weight(T::WeightInfo::burn())]
+		pub fn burn(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			token_id: TokenId,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::do_burn(caller, collection_id, token_id)
+		}
+
+		/// Transfers the token from the caller to the given destination.
+		#[pallet::weight(T::WeightInfo::transfer())]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			dest: AccountIdLookupOf<T>,
+			collection_id: CollectionId,
+			token_id: TokenId,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			let token =
+				Tokens::<T>::get(collection_id, token_id).ok_or(Error::<T>::TokenNotFound)?;
+			ensure!(Self::approved_or_owner(&caller, &token), Error::<T>::NotApproved);
+
+			Self::do_transfer(&caller, &dest, collection_id, token_id)
+		}
+
+		/// Transfers an approved or owned token from `from` to `to`.
+		#[pallet::weight(T::WeightInfo::transfer())]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: AccountIdLookupOf<T>,
+			to: AccountIdLookupOf<T>,
+			collection_id: CollectionId,
+			token_id: TokenId,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(to)?;
+
+			let token =
+				Tokens::<T>::get(collection_id, token_id).ok_or(Error::<T>::TokenNotFound)?;
+			ensure!(Self::approved_or_owner(&caller, &token), Error::<T>::NotApproved);
+
+			Self::do_transfer(&from, &to, collection_id, token_id)
+		}
+
+		/// Approves `to` to transfer the token on behalf of the caller. `None` clears the
+		/// approval.
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			to: Option<AccountIdLookupOf<T>>,
+			collection_id: CollectionId,
+			token_id: TokenId,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let to = to.map(T::Lookup::lookup).transpose()?;
+
+			Tokens::<T>::try_mutate(collection_id, token_id, |maybe_token| -> DispatchResult {
+				let token = maybe_token.as_mut().ok_or(Error::<T>::TokenNotFound)?;
+				ensure!(token.owner == caller, Error::<T>::NotAllowed);
+				token.approval = to.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::Approval { from: caller, to, collection_id, token_id });
+			Ok(())
+		}
+
+		/// Sets the metadata of a token. Only the owner or the approved account can do this.
+		#[pallet::weight(T::WeightInfo::set_metadata())]
+		pub fn set_metadata(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			token_id: TokenId,
+			metadata: Vec<u8>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let metadata: BoundedStringOf<T> =
+				metadata.try_into().map_err(|_| Error::<T>::StringTooLong)?;
+
+			Tokens::<T>::try_mutate(collection_id, token_id, |maybe_token| -> DispatchResult {
+				let token = maybe_token.as_mut().ok_or(Error::<T>::TokenNotFound)?;
+				ensure!(Self::approved_or_owner(&caller, token), Error::<T>::NotAllowed);
+				token.metadata = Some(metadata.clone());
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MetadataSet { collection_id, token_id, metadata });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the owner of the token.
+	pub fn owner_of(collection_id: CollectionId, token_id: TokenId) -> Option<T::AccountId> {
+		Tokens::<T>::get(collection_id, token_id).map(|token| token.owner)
+	}
+
+	/// Returns the tokens owned by `account`, skipping the first `start` and returning at most
+	/// `limit`, capped at [`MAX_TOKENS_PER_PAGE`].
+	///
+	/// Tokens are in storage order, which only changes when the account's tokens change.
+	pub fn tokens_of(
+		account: &T::AccountId,
+		start: u32,
+		limit: u32,
+	) -> Vec<(CollectionId, TokenId)> {
+		OwnedTokens::<T>::iter_key_prefix(account)
+			.skip(start as usize)
+			.take(limit.min(MAX_TOKENS_PER_PAGE) as usize)
+			.collect()
+	}
+
+	/// Returns true if `who` is the owner of the token or has been approved on behalf of the
+	/// owner.
+	pub fn approved_or_owner(who: &T::AccountId, token: &TokenInfoOf<T>) -> bool {
+		&token.owner == who || token.approval.as_ref() == Some(who)
+	}
+
+	/// Creates a collection owned by `owner` and returns its ID. No deposit is reserved.
+	pub fn do_create_collection(
+		owner: T::AccountId,
+		name: Vec<u8>,
+		symbol: Vec<u8>,
+	) -> Result<CollectionId, DispatchError> {
+		let name: BoundedStringOf<T> = name.try_into().map_err(|_| Error::<T>::StringTooLong)?;
+		let symbol: BoundedStringOf<T> =
+			symbol.try_into().map_err(|_| Error::<T>::StringTooLong)?;
+
+		let collection_id =
+			NextCollectionId::<T>::try_mutate(|id| -> Result<CollectionId, DispatchError> {
+				let current = *id;
+				*id = id.checked_add(1).ok_or(Error::<T>::NoAvailableCollectionId)?;
+				Ok(current)
+			})?;
+
+		Collections::<T>::insert(
+			collection_id,
+			CollectionInfo { owner: owner.clone(), name, symbol, total_supply: 0 },
+		);
+
+		Self::deposit_event(Event::CollectionCreated { collection_id, owner });
+		Ok(collection_id)
+	}
+
+	/// Mints a token to `to` without checking the caller. No deposit is reserved.
+	pub fn do_mint(
+		to: T::AccountId,
+		collection_id: CollectionId,
+		token_id: TokenId,
+		metadata: Option<Vec<u8>>,
+	) -> DispatchResult {
+		ensure!(!Tokens::<T>::contains_key(collection_id, token_id), Error::<T>::TokenExists);
+		let metadata: Option<BoundedStringOf<T>> = metadata
+			.map(TryInto::try_into)
+			.transpose()
+			.map_err(|_| Error::<T>::StringTooLong)?;
+
+		Collections::<T>::try_mutate(collection_id, |maybe_collection| -> DispatchResult {
+			let collection = maybe_collection.as_mut().ok_or(Error::<T>::CollectionNotFound)?;
+			collection.total_supply = collection.total_supply.saturating_add(1);
+			Ok(())
+		})?;
+
+		Tokens::<T>::insert(
+			collection_id,
+			token_id,
+			TokenInfo { owner: to.clone(), approval: None, metadata },
+		);
+		OwnedTokens::<T>::insert(&to, (collection_id, token_id), ());
+		OwnedTokensCount::<T>::mutate(&to, |count| *count = count.saturating_add(1));
+
+		Self::deposit_event(Event::Minted { owner: to, collection_id, token_id });
+		Ok(())
+	}
+
+	/// Burns a token owned by `caller` and returns its deposit, if any.
+	pub fn do_burn(
+		caller: T::AccountId,
+		collection_id: CollectionId,
+		token_id: TokenId,
+	) -> DispatchResult {
+		let token = Tokens::<T>::get(collection_id, token_id).ok_or(Error::<T>::TokenNotFound)?;
+		ensure!(token.owner == caller, Error::<T>::NotOwner);
+
+		Tokens::<T>::remove(collection_id, token_id);
+		if let Some((depositor, deposit)) = TokenDeposits::<T>::take(collection_id, token_id) {
+			T::Currency::unreserve(&depositor, deposit);
+		}
+		OwnedTokens::<T>::remove(&caller, (collection_id, token_id));
+		Self::decrease_counter_of(&caller);
+		Collections::<T>::mutate(collection_id, |maybe_collection| {
+			if let Some(collection) = maybe_collection {
+				collection.total_supply = collection.total_supply.saturating_sub(1);
+			}
+		});
+
+		Self::deposit_event(Event::Burned { caller, collection_id, token_id });
+		Ok(())
+	}
+
+	/// Transfers a token owned by `from` to `to` and clears its approval.
+	///
+	/// The caller must make sure the transfer is authorized.
+	pub fn do_transfer(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		collection_id: CollectionId,
+		token_id: TokenId,
+	) -> DispatchResult {
+		Tokens::<T>::try_mutate(collection_id, token_id, |maybe_token| -> DispatchResult {
+			let token = maybe_token.as_mut().ok_or(Error::<T>::TokenNotFound)?;
+			ensure!(&token.owner == from, Error::<T>::NotOwner);
+
+			token.approval = None;
+			if from != to {
+				token.owner = to.clone();
+				OwnedTokens::<T>::remove(from, (collection_id, token_id));
+				OwnedTokens::<T>::insert(to, (collection_id, token_id), ());
+				Self::decrease_counter_of(from);
+				OwnedTokensCount::<T>::mutate(to, |count| *count = count.saturating_add(1));
+			}
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::Transfer {
+			from: from.clone(),
+			to: to.clone(),
+			collection_id,
+			token_id,
+		});
+		Ok(())
+	}
+
+	/// Decreases the token counter of `of`, removing the entry once it reaches zero.
+	fn decrease_counter_of(of: &T::AccountId) {
+		OwnedTokensCount::<T>::mutate_exists(of, |count| {
+			*count = count.and_then(|c| c.checked_sub(1)).filter(|c| *c > 0);
+		});
+	}
+}
+
+impl<T: Config> Inspect<T::AccountId> for Pallet<T> {
+	type ClassId = CollectionId;
+	type InstanceId = TokenId;
+
+	fn owner(class: &Self::ClassId, instance: &Self::InstanceId) -> Option<T::AccountId> {
+		Self::owner_of(*class, *instance)
+	}
+
+	fn class_owner(class: &Self::ClassId) -> Option<T::AccountId> {
+		Collections::<T>::get(class).map(|collection| collection.owner)
+	}
+}
+
+impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(
+		class: &Self::ClassId,
+		instance: &Self::InstanceId,
+		destination: &T::AccountId,
+	) -> DispatchResult {
+		let owner = Self::owner_of(*class, *instance).ok_or(Error::<T>::TokenNotFound)?;
+		Self::do_transfer(&owner, destination, *class, *instance)
+	}
+}
@@ -0,0 +1,268 @@
+//! Unit tests for the Solar NFT pallet.
+
+use crate::{mock::*, Error, Event, GenesisConfig};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GenesisBuild, ReservableCurrency},
+};
+
+#[test]
+fn genesis_config_works() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	GenesisConfig::<Test> {
+		collections: vec![(ALICE, b"hello".to_vec(), b"world".to_vec())],
+		tokens: vec![(0, 1, BOB, Some(b"ipfs://1".to_vec())), (0, 2, BOB, None)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		assert_eq!(Nft::next_collection_id(), 1);
+		assert_eq!(Nft::collection(0).unwrap().total_supply, 2);
+		assert_eq!(Nft::owner_of(0, 1), Some(BOB));
+		assert_eq!(Nft::balance_of(BOB), 2);
+		assert_eq!(Nft::token(0, 1).unwrap().metadata.unwrap().into_inner(), b"ipfs://1".to_vec());
+	});
+}
+
+#[test]
+fn create_collection_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create_collection(Origin::signed(BOB), b"foo".to_vec(), b"FOO".to_vec()));
+		System::assert_last_event(
+			Event::<Test>::CollectionCreated { collection_id: 1, owner: BOB }.into(),
+		);
+
+		let collection = Nft::collection(1).unwrap();
+		assert_eq!(collection.owner, BOB);
+		assert_eq!(collection.symbol.into_inner(), b"FOO".to_vec());
+		assert_eq!(Nft::next_collection_id(), 2);
+	});
+}
+
+#[test]
+fn create_collection_reserves_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create_collection(Origin::signed(BOB), b"foo".to_vec(), b"FOO".to_vec()));
+		assert_eq!(Balances::reserved_balance(BOB), 10);
+
+		assert_noop!(
+			Nft::create_collection(Origin::signed(CHARLIE), b"bar".to_vec(), b"BAR".to_vec()),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn create_collection_rejects_long_name() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Nft::create_collection(Origin::signed(BOB), vec![0u8; 51], b"FOO".to_vec()),
+			Error::<Test>::StringTooLong
+		);
+	});
+}
+
+#[test]
+fn mint_and_check_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, 123, None));
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, 223, None));
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, 323, None));
+		System::assert_last_event(
+			Event::<Test>::Minted { owner: BOB, collection_id: 0, token_id: 323 }.into(),
+		);
+
+		assert_eq!(Nft::owner_of(0, 223), Some(BOB));
+		assert_eq!(Nft::balance_of(BOB), 3);
+		assert_eq!(Nft::collection(0).unwrap().total_supply, 3);
+
+		let mut tokens = Nft::tokens_of(&BOB, 0, 10);
+		tokens.sort();
+		assert_eq!(tokens, vec![(0, 123), (0, 223), (0, 323)]);
+	});
+}
+
+#[test]
+fn mint_reserves_deposit_and_burn_returns_it() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, 1, None));
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, 2, None));
+		// The collection owner pays for the tokens, not the receiver.
+		assert_eq!(Balances::reserved_balance(ALICE), 2);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+
+		// The deposit goes back to the collection owner even if someone else burns the token.
+		assert_ok!(Nft::burn(Origin::signed(BOB), 0, 1));
+		assert_eq!(Balances::reserved_balance(ALICE), 1);
+		assert_eq!(Balances::free_balance(ALICE), 99);
+	});
+}
+
+#[test]
+fn genesis_tokens_burn_without_deposit() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(ALICE, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	GenesisConfig::<Test> {
+		collections: vec![(ALICE, b"hello".to_vec(), b"world".to_vec())],
+		tokens: vec![(0, 1, BOB, None)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		assert_ok!(Balances::reserve(&ALICE, 5));
+		assert_ok!(Nft::burn(Origin::signed(BOB), 0, 1));
+		// Funds ALICE reserved for something else are left alone.
+		assert_eq!(Balances::reserved_balance(ALICE), 5);
+	});
+}
+
+#[test]
+fn tokens_of_pages() {
+	new_test_ext().execute_with(|| {
+		for token_id in 1..=3 {
+			assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, token_id, None));
+		}
+
+		let first = Nft::tokens_of(&BOB, 0, 2);
+		let second = Nft::tokens_of(&BOB, 2, 2);
+		assert_eq!(first.len(), 2);
+		assert_eq!(second.len(), 1);
+		let mut all = [first, second].concat();
+		all.sort();
+		assert_eq!(all, vec![(0, 1), (0, 2), (0, 3)]);
+		assert!(Nft::tokens_of(&BOB, 3, 2).is_empty());
+	});
+}
+
+#[test]
+fn mint_existing_token_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, 1, None));
+		assert_noop!(
+			Nft::mint(Origin::signed(ALICE), CHARLIE, 0, 1, None),
+			Error::<Test>::TokenExists
+		);
+	});
+}
+
+#[test]
+fn only_collection_owner_can_mint() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Nft::mint(Origin::signed(BOB), BOB, 0, 1, None), Error::<Test>::NotOwner);
+		assert_noop!(
+			Nft::mint(Origin::signed(ALICE), BOB, 7, 1, None),
+			Error::<Test>::CollectionNotFound
+		);
+	});
+}
+
+#[test]
+fn mint_and_transfer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, 1, None));
+		assert_ok!(Nft::transfer(Origin::signed(BOB), CHARLIE, 0, 1));
+		System::assert_last_event(
+			Event::<Test>::Transfer { from: BOB, to: CHARLIE, collection_id: 0, token_id: 1 }
+				.into(),
+		);
+
+		assert_eq!(Nft::owner_of(0, 1), Some(CHARLIE));
+		assert_eq!(Nft::balance_of(BOB), 0);
+		assert_eq!(Nft::balance_of(CHARLIE), 1);
+		assert!(Nft::tokens_of(&BOB, 0, 10).is_empty());
+		assert_eq!(Nft::tokens_of(&CHARLIE, 0, 10), vec![(0, 1)]);
+	});
+}
+
+#[test]
+fn transfer_not_approved_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, 1, None));
+		assert_noop!(
+			Nft::transfer(Origin::signed(CHARLIE), CHARLIE, 0, 1),
+			Error::<Test>::NotApproved
+		);
+		assert_noop!(
+			Nft::transfer(Origin::signed(BOB), CHARLIE, 0, 2),
+			Error::<Test>::TokenNotFound
+		);
+	});
+}
+
+#[test]
+fn approved_account_can_transfer_from() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, 1, None));
+		assert_ok!(Nft::approve(Origin::signed(BOB), Some(CHARLIE), 0, 1));
+		System::assert_last_event(
+			Event::<Test>::Approval { from: BOB, to: Some(CHARLIE), collection_id: 0, token_id: 1 }
+				.into(),
+		);
+
+		assert_noop!(
+			Nft::transfer_from(Origin::signed(CHARLIE), ALICE, CHARLIE, 0, 1),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(Nft::transfer_from(Origin::signed(CHARLIE), BOB, CHARLIE, 0, 1));
+		assert_eq!(Nft::owner_of(0, 1), Some(CHARLIE));
+
+		// The approval is cleared by the transfer.
+		assert_eq!(Nft::token(0, 1).unwrap().approval, None);
+	});
+}
+
+#[test]
+fn only_owner_can_approve() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, 1, None));
+		assert_noop!(
+			Nft::approve(Origin::signed(CHARLIE), Some(CHARLIE), 0, 1),
+			Error::<Test>::NotAllowed
+		);
+
+		assert_ok!(Nft::approve(Origin::signed(BOB), Some(CHARLIE), 0, 1));
+		assert_ok!(Nft::approve(Origin::signed(BOB), None, 0, 1));
+		assert_eq!(Nft::token(0, 1).unwrap().approval, None);
+	});
+}
+
+#[test]
+fn burn_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, 1, None));
+		assert_noop!(Nft::burn(Origin::signed(ALICE), 0, 1), Error::<Test>::NotOwner);
+
+		assert_ok!(Nft::burn(Origin::signed(BOB), 0, 1));
+		System::assert_last_event(
+			Event::<Test>::Burned { caller: BOB, collection_id: 0, token_id: 1 }.into(),
+		);
+
+		assert_eq!(Nft::owner_of(0, 1), None);
+		assert_eq!(Nft::balance_of(BOB), 0);
+		assert_eq!(Nft::collection(0).unwrap().total_supply, 0);
+		assert_noop!(Nft::burn(Origin::signed(BOB), 0, 1), Error::<Test>::TokenNotFound);
+	});
+}
+
+#[test]
+fn set_metadata_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB, 0, 1, None));
+		assert_noop!(
+			Nft::set_metadata(Origin::signed(CHARLIE), 0, 1, b"ipfs://1".to_vec()),
+			Error::<Test>::NotAllowed
+		);
+		assert_noop!(
+			Nft::set_metadata(Origin::signed(BOB), 0, 1, vec![0u8; 51]),
+			Error::<Test>::StringTooLong
+		);
+
+		assert_ok!(Nft::approve(Origin::signed(BOB), Some(CHARLIE), 0, 1));
+		assert_ok!(Nft::set_metadata(Origin::signed(CHARLIE), 0, 1, b"ipfs://1".to_vec()));
+		assert_eq!(Nft::token(0, 1).unwrap().metadata.unwrap().into_inner(), b"ipfs://1".to_vec());
+	});
+}
@@ -0,0 +1,115 @@
+//! Weights for pallet_solar_nft
+//!
+//! PLACEHOLDER, not benchmark output: hand-written weights, a flat cost plus the storage accesses
+//! of each extrinsic. `scripts/benchmark.sh` measures the benchmarks in `benchmarking.rs`; copy
+//! its output for this pallet here once it has run on the reference machine.
+//!
+//! The flat costs of calls any signed account can make are set several times above what
+//! Substrate measured for comparable calls, such as `pallet_uniques::mint`, so that they are not
+//! underpriced until then.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_solar_nft.
+pub trait WeightInfo {
+	fn create_collection() -> Weight;
+	fn mint() -> Weight;
+	fn burn() -> Weight;
+	fn transfer() -> Weight;
+	fn approve() -> Weight;
+	fn set_metadata() -> Weight;
+}
+
+/// Placeholder weights for pallet_solar_nft, see the module docs.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: System Account (r:1 w:1)
+	// Storage: Nft NextCollectionId (r:1 w:1)
+	// Storage: Nft Collections (r:0 w:1)
+	fn create_collection() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: Nft Tokens (r:1 w:1)
+	// Storage: Nft Collections (r:1 w:1)
+	// Storage: Nft OwnedTokensCount (r:1 w:1)
+	// Storage: Nft OwnedTokens (r:0 w:1)
+	// Storage: Nft TokenDeposits (r:0 w:1)
+	fn mint() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Nft Tokens (r:1 w:1)
+	// Storage: Nft TokenDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Nft Collections (r:1 w:1)
+	// Storage: Nft OwnedTokensCount (r:1 w:1)
+	// Storage: Nft OwnedTokens (r:0 w:1)
+	fn burn() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Nft Tokens (r:1 w:1)
+	// Storage: Nft OwnedTokensCount (r:2 w:2)
+	// Storage: Nft OwnedTokens (r:0 w:2)
+	fn transfer() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Nft Tokens (r:1 w:1)
+	fn approve() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Nft Tokens (r:1 w:1)
+	fn set_metadata() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_collection() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn mint() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn burn() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn transfer() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn approve() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_metadata() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,156 @@
+//! Benchmarks for `pallet_node_authorization`, which ships without any.
+//!
+//! Like `pallet_session_benchmarking`, this crate wraps the pallet in its own `Pallet` type so the
+//! benchmarks can be implemented outside of it. Each benchmark measures the worst case its
+//! extrinsic allows: a full set of well-known nodes and peer IDs of the maximum length.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg(feature = "runtime-benchmarks")]
+
+#[cfg(test)]
+mod mock;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{EnsureOrigin, Get};
+use frame_system::RawOrigin;
+use pallet_node_authorization::{AdditionalConnections, Call, Owners, WellKnownNodes};
+use sp_core::OpaquePeerId as PeerId;
+use sp_std::{collections::btree_set::BTreeSet, prelude::*};
+
+pub struct Pallet<T: Config>(pallet_node_authorization::Pallet<T>);
+pub trait Config: pallet_node_authorization::Config {}
+
+/// The `i`th peer ID, one byte shorter than `MaxPeerIdLength` as the pallet requires.
+fn peer_id<T: Config>(i: u32) -> PeerId {
+	let mut id = i.to_le_bytes().to_vec();
+	id.resize(T::MaxPeerIdLength::get().saturating_sub(1).max(4) as usize, 0);
+	PeerId(id)
+}
+
+/// Makes `n` nodes well-known, each with its own owner, and returns them.
+fn set_well_known_nodes<T: Config>(n: u32) -> Vec<PeerId> {
+	let nodes: Vec<PeerId> = (0..n).map(peer_id::<T>).collect();
+	for (i, node) in nodes.iter().enumerate() {
+		Owners::<T>::insert(node, account::<T::AccountId>("owner", i as u32, 0));
+	}
+	WellKnownNodes::<T>::put(nodes.iter().cloned().collect::<BTreeSet<_>>());
+	nodes
+}
+
+/// `MaxWellKnownNodes` peer IDs other than `node`, to connect it to.
+fn connections<T: Config>() -> Vec<PeerId> {
+	(1..=T::MaxWellKnownNodes::get()).map(|i| peer_id::<T>(u32::MAX - i)).collect()
+}
+
+/// Makes `owner` the owner of `node`, with `connections` as additional connections.
+fn claim<T: Config>(owner: &T::AccountId, node: &PeerId, connections: Vec<PeerId>) {
+	Owners::<T>::insert(node, owner);
+	AdditionalConnections::<T>::insert(node, connections.into_iter().collect::<BTreeSet<_>>());
+}
+
+benchmarks! {
+	add_well_known_node {
+		let max = T::MaxWellKnownNodes::get();
+		set_well_known_nodes::<T>(max - 1);
+		let node = peer_id::<T>(max);
+		let owner: T::AccountId = account("owner", max, 0);
+		let origin = T::AddOrigin::successful_origin();
+	}: _<T::Origin>(origin, node.clone(), owner.clone())
+	verify {
+		assert!(WellKnownNodes::<T>::get().contains(&node));
+		assert_eq!(Owners::<T>::get(&node), Some(owner));
+	}
+
+	remove_well_known_node {
+		let node = set_well_known_nodes::<T>(T::MaxWellKnownNodes::get()).pop().unwrap();
+		claim::<T>(&account("owner", T::MaxWellKnownNodes::get() - 1, 0), &node, connections::<T>());
+		let origin = T::RemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, node.clone())
+	verify {
+		assert!(!WellKnownNodes::<T>::get().contains(&node));
+		assert!(!Owners::<T>::contains_key(&node));
+	}
+
+	swap_well_known_node {
+		let max = T::MaxWellKnownNodes::get();
+		let remove = set_well_known_nodes::<T>(max).pop().unwrap();
+		claim::<T>(&account("owner", max - 1, 0), &remove, connections::<T>());
+		let add = peer_id::<T>(max);
+		let origin = T::SwapOrigin::successful_origin();
+	}: _<T::Origin>(origin, remove.clone(), add.clone())
+	verify {
+		let nodes = WellKnownNodes::<T>::get();
+		assert!(!nodes.contains(&remove));
+		assert!(nodes.contains(&add));
+	}
+
+	// The worst case replaces a full set with another one.
+	reset_well_known_nodes {
+		let max = T::MaxWellKnownNodes::get();
+		set_well_known_nodes::<T>(max - 1);
+		let nodes: Vec<(PeerId, T::AccountId)> = (max..2 * max - 1)
+			.map(|i| (peer_id::<T>(i), account("owner", i, 0)))
+			.collect();
+		let origin = T::ResetOrigin::successful_origin();
+	}: _<T::Origin>(origin, nodes.clone())
+	verify {
+		assert_eq!(
+			WellKnownNodes::<T>::get(),
+			nodes.into_iter().map(|(node, _)| node).collect::<BTreeSet<_>>(),
+		);
+	}
+
+	claim_node {
+		let caller: T::AccountId = whitelisted_caller();
+		let node = peer_id::<T>(0);
+	}: _(RawOrigin::Signed(caller.clone()), node.clone())
+	verify {
+		assert_eq!(Owners::<T>::get(&node), Some(caller));
+	}
+
+	remove_claim {
+		let caller: T::AccountId = whitelisted_caller();
+		let node = peer_id::<T>(0);
+		claim::<T>(&caller, &node, connections::<T>());
+	}: _(RawOrigin::Signed(caller), node.clone())
+	verify {
+		assert!(!Owners::<T>::contains_key(&node));
+		assert!(!AdditionalConnections::<T>::contains_key(&node));
+	}
+
+	transfer_node {
+		let caller: T::AccountId = whitelisted_caller();
+		let node = peer_id::<T>(0);
+		claim::<T>(&caller, &node, vec![]);
+		let owner: T::AccountId = account("owner", 0, 0);
+	}: _(RawOrigin::Signed(caller), node.clone(), owner.clone())
+	verify {
+		assert_eq!(Owners::<T>::get(&node), Some(owner));
+	}
+
+	// Connects the node to `MaxWellKnownNodes` peers.
+	add_connections {
+		let caller: T::AccountId = whitelisted_caller();
+		let node = peer_id::<T>(0);
+		claim::<T>(&caller, &node, vec![]);
+		let connections = connections::<T>();
+	}: _(RawOrigin::Signed(caller), node.clone(), connections.clone())
+	verify {
+		assert_eq!(
+			AdditionalConnections::<T>::get(&node),
+			connections.into_iter().collect::<BTreeSet<_>>(),
+		);
+	}
+
+	// Disconnects the node from `MaxWellKnownNodes` peers.
+	remove_connections {
+		let caller: T::AccountId = whitelisted_caller();
+		let node = peer_id::<T>(0);
+		claim::<T>(&caller, &node, connections::<T>());
+	}: _(RawOrigin::Signed(caller), node.clone(), connections::<T>())
+	verify {
+		assert!(AdditionalConnections::<T>::get(&node).is_empty());
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
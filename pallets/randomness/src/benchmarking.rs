@@ -0,0 +1,62 @@
+//! Benchmarks for the Solar Randomness pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+
+/// Endows `who` with enough to reserve the commit deposit.
+fn fund<T: Config>(who: &T::AccountId) {
+	let amount = T::CommitDeposit::get()
+		.saturating_add(T::Currency::minimum_balance())
+		.saturating_mul(10u32.into());
+	T::Currency::make_free_balance_be(who, amount);
+}
+
+/// Commits `who` to `secret` at block 1.
+fn commit_to<T: Config>(who: &T::AccountId, secret: &T::Hash) {
+	fund::<T>(who);
+	frame_system::Pallet::<T>::set_block_number(One::one());
+	let hash = Pallet::<T>::commitment_hash(who, secret);
+	Pallet::<T>::commit(RawOrigin::Signed(who.clone()).into(), hash).unwrap();
+}
+
+benchmarks! {
+	commit {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let hash = Pallet::<T>::commitment_hash(&caller, &T::Hashing::hash(b"secret"));
+	}: _(RawOrigin::Signed(caller.clone()), hash)
+	verify {
+		assert!(Commitments::<T>::contains_key(&caller));
+	}
+
+	// The worst case mixes the secret into a pending seed.
+	reveal {
+		let caller: T::AccountId = whitelisted_caller();
+		let secret = T::Hashing::hash(b"secret");
+		commit_to::<T>(&caller, &secret);
+		let reveal_at = T::RevealDelay::get().saturating_add(One::one());
+		let seed_at = reveal_at.saturating_add(T::SeedDelay::get().max(One::one()));
+		PendingSeeds::<T>::insert(seed_at, T::Hash::default());
+		frame_system::Pallet::<T>::set_block_number(reveal_at);
+	}: _(RawOrigin::Signed(caller.clone()), secret)
+	verify {
+		assert!(!Commitments::<T>::contains_key(&caller));
+	}
+
+	clear_expired {
+		let who: T::AccountId = account("committer", 0, 0);
+		commit_to::<T>(&who, &T::Hashing::hash(b"secret"));
+		let expired_at = T::RevealWindow::get().saturating_add(2u32.into());
+		frame_system::Pallet::<T>::set_block_number(expired_at);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), who.clone())
+	verify {
+		assert!(!Commitments::<T>::contains_key(&who));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
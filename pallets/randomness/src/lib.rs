@@ -0,0 +1,254 @@
+//! # Solar Randomness Pallet
+//!
+//! A commit-reveal randomness source for contracts, replacing `pallet_randomness_collective_flip`
+//! whose output depends on nothing but recent block hashes.
+//!
+//! Participants `commit` the hash of `(account, secret)` together with a deposit, and `reveal`
+//! the secret at least `RevealDelay` and at most `RevealWindow` blocks later. Since a secret is
+//! bound to an earlier commitment, the revealer cannot choose it after seeing the seed.
+//!
+//! At the start of every block the seed is hashed with the parent block hash, so it changes
+//! each block even when nobody reveals. Secrets revealed in block `n` enter the seed only at the
+//! start of block `n + SeedDelay`, after the hashes of the blocks in between, so whoever reveals,
+//! or includes a reveal, decides without knowing which seed the secret will produce. Nothing
+//! included in a block changes the randomness that block hands out.
+//!
+//! The author of the parent block is not bound this way: it knows the seed of the next block
+//! before sealing its own, and can bias it by trying different contents for its block, or by not
+//! producing the block, until the parent hash gives a seed it likes. Revealed secrets do not take
+//! that away, since they are known `SeedDelay` blocks before they enter the seed; when none are
+//! revealed the seed is only as good as the parent hash. Aura gives the runtime nothing the author
+//! cannot control, so contracts must not rely on this pallet where a single block author could
+//! profit from choosing among outcomes.
+//!
+//! Other participants can still withhold: a committer, or an author censoring reveals, can keep
+//! a secret out of the seed. Unrevealed commitments expire after `RevealWindow` and anyone can
+//! then `clear_expired` them, slashing the deposit, so that choice has a price.
+//!
+//! `Randomness::random` returns the hash of the subject and the seed, along with the parent
+//! block, after which the seed could be known.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{Currency, OnUnbalanced, Randomness, ReservableCurrency},
+	RuntimeDebug,
+};
+use scale_info::TypeInfo;
+use sp_runtime::traits::{Hash, One, Saturating};
+pub use weights::WeightInfo;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+pub type CommitmentOf<T> = Commitment<
+	<T as frame_system::Config>::Hash,
+	<T as frame_system::Config>::BlockNumber,
+	BalanceOf<T>,
+>;
+
+/// A secret waiting to be revealed.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Commitment<Hash, BlockNumber, Balance> {
+	/// The hash of the committer's account and secret.
+	pub hash: Hash,
+	/// The block the commitment was made in.
+	pub block: BlockNumber,
+	/// The deposit reserved until the secret is revealed.
+	pub deposit: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency commitment deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved for a commitment.
+		#[pallet::constant]
+		type CommitDeposit: Get<BalanceOf<Self>>;
+
+		/// The number of blocks that must pass between a commitment and its reveal.
+		#[pallet::constant]
+		type RevealDelay: Get<Self::BlockNumber>;
+
+		/// The number of blocks after the commitment within which the secret must be revealed.
+		#[pallet::constant]
+		type RevealWindow: Get<Self::BlockNumber>;
+
+		/// The number of blocks after a reveal before its secret enters the seed, at least one.
+		#[pallet::constant]
+		type SeedDelay: Get<Self::BlockNumber>;
+
+		/// Handler for the deposits of expired commitments.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Open commitments by account.
+	#[pallet::storage]
+	#[pallet::getter(fn commitment)]
+	pub type Commitments<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, CommitmentOf<T>, OptionQuery>;
+
+	/// Revealed secrets by the block at the start of which they are mixed into `Seed`.
+	#[pallet::storage]
+	pub type PendingSeeds<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, T::Hash, OptionQuery>;
+
+	/// The seed randomness is derived from in this block.
+	#[pallet::storage]
+	#[pallet::getter(fn seed)]
+	pub type Seed<T: Config> = StorageValue<_, T::Hash, ValueQuery>;
+
+	/// The parent of the current block, the last block whose hash went into `Seed`.
+	#[pallet::storage]
+	#[pallet::getter(fn seed_block)]
+	pub type SeedBlock<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A secret was committed.
+		Committed { who: T::AccountId, hash: T::Hash },
+		/// A secret was revealed. It is part of the seed `SeedDelay` blocks later.
+		Revealed { who: T::AccountId },
+		/// An expired commitment was cleared and its deposit slashed.
+		CommitmentSlashed { who: T::AccountId, amount: BalanceOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account already has an open commitment.
+		AlreadyCommitted,
+		/// The account has no open commitment.
+		NoCommitment,
+		/// `RevealDelay` has not passed since the commitment.
+		TooEarly,
+		/// The commitment expired and can no longer be revealed.
+		Expired,
+		/// The commitment has not expired yet.
+		NotExpired,
+		/// The secret does not match the commitment.
+		BadReveal,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let parent_hash = frame_system::Pallet::<T>::parent_hash();
+			let revealed = PendingSeeds::<T>::take(n);
+			Seed::<T>::mutate(|seed| *seed = T::Hashing::hash_of(&(*seed, parent_hash, revealed)));
+			SeedBlock::<T>::put(n.saturating_sub(One::one()));
+			T::DbWeight::get().reads_writes(3, 3)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Commits to a secret, given as the hash of the signer and the secret, and reserves
+		/// `CommitDeposit`.
+		#[pallet::weight(T::WeightInfo::commit())]
+		pub fn commit(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Commitments::<T>::contains_key(&who), Error::<T>::AlreadyCommitted);
+
+			let deposit = T::CommitDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			let block = frame_system::Pallet::<T>::block_number();
+			Commitments::<T>::insert(&who, Commitment { hash, block, deposit });
+
+			Self::deposit_event(Event::Committed { who, hash });
+			Ok(())
+		}
+
+		/// Reveals the secret of the signer's commitment and returns the deposit.
+		#[pallet::weight(T::WeightInfo::reveal())]
+		pub fn reveal(origin: OriginFor<T>, secret: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let commitment = Commitments::<T>::get(&who).ok_or(Error::<T>::NoCommitment)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				now >= commitment.block.saturating_add(T::RevealDelay::get()),
+				Error::<T>::TooEarly
+			);
+			ensure!(!Self::is_expired(&commitment, now), Error::<T>::Expired);
+			ensure!(commitment.hash == Self::commitment_hash(&who, &secret), Error::<T>::BadReveal);
+
+			let at = now.saturating_add(T::SeedDelay::get().max(One::one()));
+			PendingSeeds::<T>::mutate(at, |pending| {
+				*pending = Some(T::Hashing::hash_of(&(pending.unwrap_or_default(), secret)))
+			});
+			T::Currency::unreserve(&who, commitment.deposit);
+			Commitments::<T>::remove(&who);
+
+			Self::deposit_event(Event::Revealed { who });
+			Ok(())
+		}
+
+		/// Removes an expired commitment of `who` and slashes its deposit.
+		#[pallet::weight(T::WeightInfo::clear_expired())]
+		pub fn clear_expired(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let commitment = Commitments::<T>::get(&who).ok_or(Error::<T>::NoCommitment)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(Self::is_expired(&commitment, now), Error::<T>::NotExpired);
+
+			let (imbalance, _) = T::Currency::slash_reserved(&who, commitment.deposit);
+			T::Slash::on_unbalanced(imbalance);
+			Commitments::<T>::remove(&who);
+
+			Self::deposit_event(Event::CommitmentSlashed { who, amount: commitment.deposit });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The hash `who` commits to for `secret`.
+	pub fn commitment_hash(who: &T::AccountId, secret: &T::Hash) -> T::Hash {
+		T::Hashing::hash_of(&(who, secret))
+	}
+
+	fn is_expired(commitment: &CommitmentOf<T>, now: T::BlockNumber) -> bool {
+		now > commitment.block.saturating_add(T::RevealWindow::get())
+	}
+}
+
+impl<T: Config> Randomness<T::Hash, T::BlockNumber> for Pallet<T> {
+	fn random(subject: &[u8]) -> (T::Hash, T::BlockNumber) {
+		(T::Hashing::hash_of(&(subject, Self::seed())), Self::seed_block())
+	}
+}
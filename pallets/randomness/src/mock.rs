@@ -0,0 +1,110 @@
+//! Test environment for the Solar Randomness pallet.
+
+use super::*;
+use crate as pallet_solar_randomness;
+
+use frame_support::{
+	construct_runtime,
+	traits::{ConstU32, ConstU64, Hooks},
+};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		CommitReveal: pallet_solar_randomness::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type CommitDeposit = ConstU64<10>;
+	type RevealDelay = ConstU64<1>;
+	type RevealWindow = ConstU64<5>;
+	type SeedDelay = ConstU64<2>;
+	type Slash = ();
+	type WeightInfo = ();
+}
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+
+/// `ALICE` and `BOB` are endowed with 100 units.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(ALICE, 100), (BOB, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Moves to block `n`, running `on_initialize` of the pallet for each block.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		let parent_hash = H256::from_low_u64_be(System::block_number());
+		next_block(parent_hash);
+	}
+}
+
+/// Moves to the next block, a child of the block with `parent_hash`.
+pub fn next_block(parent_hash: H256) {
+	let next = System::block_number() + 1;
+	System::set_block_number(next);
+	System::set_parent_hash(parent_hash);
+	CommitReveal::on_initialize(next);
+}
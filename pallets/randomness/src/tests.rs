@@ -0,0 +1,203 @@
+//! Unit tests for the Solar Randomness pallet.
+
+use crate::{mock::*, Error, Event, PendingSeeds};
+use frame_support::{assert_noop, assert_ok, traits::Randomness};
+use sp_core::H256;
+
+fn secret(n: u8) -> H256 {
+	H256::repeat_byte(n)
+}
+
+fn commit(who: u64, secret: H256) {
+	let hash = CommitReveal::commitment_hash(&who, &secret);
+	assert_ok!(CommitReveal::commit(Origin::signed(who), hash));
+}
+
+#[test]
+fn commit_reserves_deposit() {
+	new_test_ext().execute_with(|| {
+		let hash = CommitReveal::commitment_hash(&ALICE, &secret(1));
+		assert_ok!(CommitReveal::commit(Origin::signed(ALICE), hash));
+		System::assert_last_event(Event::<Test>::Committed { who: ALICE, hash }.into());
+		assert_eq!(Balances::reserved_balance(ALICE), 10);
+
+		assert_noop!(
+			CommitReveal::commit(Origin::signed(ALICE), hash),
+			Error::<Test>::AlreadyCommitted
+		);
+	});
+}
+
+#[test]
+fn reveal_returns_deposit() {
+	new_test_ext().execute_with(|| {
+		commit(ALICE, secret(1));
+		run_to_block(2);
+
+		assert_noop!(
+			CommitReveal::reveal(Origin::signed(ALICE), secret(2)),
+			Error::<Test>::BadReveal
+		);
+		assert_noop!(
+			CommitReveal::reveal(Origin::signed(BOB), secret(1)),
+			Error::<Test>::NoCommitment
+		);
+
+		assert_ok!(CommitReveal::reveal(Origin::signed(ALICE), secret(1)));
+		System::assert_last_event(Event::<Test>::Revealed { who: ALICE }.into());
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(CommitReveal::commitment(ALICE), None);
+	});
+}
+
+#[test]
+fn commitment_is_bound_to_committer() {
+	new_test_ext().execute_with(|| {
+		// Bob copies Alice's commitment, but cannot reveal it without knowing her secret.
+		let hash = CommitReveal::commitment_hash(&ALICE, &secret(1));
+		assert_ok!(CommitReveal::commit(Origin::signed(BOB), hash));
+		run_to_block(2);
+
+		assert_noop!(
+			CommitReveal::reveal(Origin::signed(BOB), secret(1)),
+			Error::<Test>::BadReveal
+		);
+	});
+}
+
+#[test]
+fn reveal_in_commit_block_is_too_early() {
+	new_test_ext().execute_with(|| {
+		commit(ALICE, secret(1));
+		assert_noop!(
+			CommitReveal::reveal(Origin::signed(ALICE), secret(1)),
+			Error::<Test>::TooEarly
+		);
+	});
+}
+
+#[test]
+fn randomness_is_fixed_within_block() {
+	new_test_ext().execute_with(|| {
+		commit(ALICE, secret(1));
+		commit(BOB, secret(2));
+		run_to_block(2);
+
+		let (before, known_since) = CommitReveal::random(b"drop");
+		assert_ok!(CommitReveal::reveal(Origin::signed(ALICE), secret(1)));
+		assert_ok!(CommitReveal::reveal(Origin::signed(BOB), secret(2)));
+		// Reveals included in this block do not change what this block hands out.
+		assert_eq!(CommitReveal::random(b"drop"), (before, known_since));
+		assert!(PendingSeeds::<Test>::contains_key(4));
+
+		run_to_block(3);
+		let (after, known_since) = CommitReveal::random(b"drop");
+		assert_ne!(after, before);
+		assert_eq!(known_since, 2);
+		assert_ne!(CommitReveal::random(b"other").0, after);
+	});
+}
+
+#[test]
+fn randomness_depends_on_every_reveal() {
+	let seed_with = |secrets: &[(u64, u8)]| {
+		new_test_ext().execute_with(|| {
+			for (who, n) in secrets {
+				commit(*who, secret(*n));
+			}
+			run_to_block(2);
+			for (who, n) in secrets {
+				assert_ok!(CommitReveal::reveal(Origin::signed(*who), secret(*n)));
+			}
+			run_to_block(4);
+			CommitReveal::seed()
+		})
+	};
+
+	let both = seed_with(&[(ALICE, 1), (BOB, 2)]);
+	assert_ne!(both, seed_with(&[(ALICE, 1)]));
+	assert_ne!(both, seed_with(&[(ALICE, 1), (BOB, 3)]));
+}
+
+#[test]
+fn reveals_enter_the_seed_after_the_delay() {
+	let seeds = |reveal: bool| {
+		new_test_ext().execute_with(|| {
+			commit(ALICE, secret(1));
+			run_to_block(2);
+			if reveal {
+				assert_ok!(CommitReveal::reveal(Origin::signed(ALICE), secret(1)));
+			}
+			run_to_block(3);
+			let delayed = CommitReveal::seed();
+			run_to_block(4);
+			(delayed, CommitReveal::seed())
+		})
+	};
+
+	let (delayed, mixed) = seeds(true);
+	let (idle_delayed, idle_mixed) = seeds(false);
+	assert_eq!(delayed, idle_delayed);
+	assert_ne!(mixed, idle_mixed);
+}
+
+#[test]
+fn randomness_changes_every_block_without_reveals() {
+	new_test_ext().execute_with(|| {
+		let (first, _) = CommitReveal::random(b"drop");
+		run_to_block(2);
+		let (second, known_since) = CommitReveal::random(b"drop");
+		run_to_block(3);
+		assert_ne!(first, second);
+		assert_ne!(second, CommitReveal::random(b"drop").0);
+		assert_eq!(known_since, 1);
+	});
+}
+
+#[test]
+fn randomness_cannot_be_predicted_before_inclusion() {
+	// Two chains with the same state, pending reveals included, that differ only in the block
+	// a transaction ends up in. A caller preparing the transaction in block 3 sees the same
+	// state on both, yet the randomness it gets depends on the hash of the block before, which
+	// only the author of that block knows before sealing it.
+	let random_in_child_of = |parent_hash: H256| {
+		new_test_ext().execute_with(|| {
+			commit(ALICE, secret(1));
+			run_to_block(2);
+			assert_ok!(CommitReveal::reveal(Origin::signed(ALICE), secret(1)));
+			run_to_block(3);
+			next_block(parent_hash);
+			CommitReveal::random(b"drop").0
+		})
+	};
+
+	let one = random_in_child_of(H256::repeat_byte(1));
+	let other = random_in_child_of(H256::repeat_byte(2));
+	assert_ne!(one, other);
+	assert_eq!(one, random_in_child_of(H256::repeat_byte(1)));
+}
+
+#[test]
+fn expired_commitment_is_slashed() {
+	new_test_ext().execute_with(|| {
+		commit(ALICE, secret(1));
+		run_to_block(6);
+		assert_noop!(
+			CommitReveal::clear_expired(Origin::signed(BOB), ALICE),
+			Error::<Test>::NotExpired
+		);
+
+		run_to_block(7);
+		assert_noop!(
+			CommitReveal::reveal(Origin::signed(ALICE), secret(1)),
+			Error::<Test>::Expired
+		);
+		assert_ok!(CommitReveal::clear_expired(Origin::signed(BOB), ALICE));
+		System::assert_last_event(
+			Event::<Test>::CommitmentSlashed { who: ALICE, amount: 10 }.into(),
+		);
+		assert_eq!(Balances::free_balance(ALICE), 90);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(CommitReveal::commitment(ALICE), None);
+	});
+}
@@ -0,0 +1,69 @@
+//! Weights for pallet_solar_randomness
+//!
+//! PLACEHOLDER, not benchmark output: hand-written weights, a flat cost plus the storage accesses
+//! of each extrinsic. `scripts/benchmark.sh` measures the benchmarks in `benchmarking.rs`; copy
+//! its output for this pallet here once it has run on the reference machine.
+//!
+//! The flat costs of calls any signed account can make are set several times above what
+//! Substrate measured for comparable calls, such as `pallet_uniques::mint`, so that they are not
+//! underpriced until then.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_solar_randomness.
+pub trait WeightInfo {
+	fn commit() -> Weight;
+	fn reveal() -> Weight;
+	fn clear_expired() -> Weight;
+}
+
+/// Placeholder weights for pallet_solar_randomness, see the module docs.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: CommitReveal Commitments (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn commit() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CommitReveal Commitments (r:1 w:1)
+	// Storage: CommitReveal PendingSeeds (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reveal() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: CommitReveal Commitments (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_expired() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn commit() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn reveal() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn clear_expired() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
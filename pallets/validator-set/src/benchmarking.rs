@@ -0,0 +1,49 @@
+//! Benchmarks for the Solar Validator Set pallet.
+//!
+//! Session keys are registered through `pallet_session`, so these benchmarks assume it is
+//! `Config::ValidatorRegistration`.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+use sp_runtime::{codec::Decode, traits::TrailingZeroInput};
+
+/// Fills the validator set with `n` accounts.
+fn set_validators<T: Config>(n: u32) -> Vec<T::AccountId> {
+	let validators: Vec<T::AccountId> = (0..n).map(|i| account("validator", i, 0)).collect();
+	Validators::<T>::put(BoundedVec::try_from(validators.clone()).unwrap());
+	validators
+}
+
+benchmarks! {
+	// The worst case fills the last free seat.
+	add_validator {
+		set_validators::<T>(T::MaxValidators::get() - 1);
+		let validator: T::AccountId = account("candidate", 0, 0);
+		frame_system::Pallet::<T>::inc_providers(&validator);
+		let keys = T::Keys::decode(&mut TrailingZeroInput::zeroes()).unwrap();
+		pallet_session::Pallet::<T>::set_keys(
+			RawOrigin::Signed(validator.clone()).into(),
+			keys,
+			vec![],
+		)?;
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, validator.clone())
+	verify {
+		assert!(Validators::<T>::get().contains(&validator));
+	}
+
+	// The worst case removes the last validator of a full set.
+	remove_validator {
+		let validator = set_validators::<T>(T::MaxValidators::get()).pop().unwrap();
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, validator.clone())
+	verify {
+		assert!(!Validators::<T>::get().contains(&validator));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
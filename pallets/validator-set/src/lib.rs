@@ -0,0 +1,258 @@
+//! # Solar Validator Set Pallet
+//!
+//! Keeps the list of validators handed to `pallet_session`, so a governance origin can add and
+//! remove authorities without a hard fork.
+//!
+//! The pallet is the `SessionManager` of `pallet_session`. Changes made during a session are
+//! picked up when the next session is planned and become active one session later, just like any
+//! other session key change.
+//!
+//! A validator can only be added once it registered its session keys with
+//! `Session::set_keys`, and the set never shrinks below `Config::MinAuthorities`, so a change can
+//! not leave Aura or Grandpa without authorities. While the set is empty, which is the case on a
+//! chain upgraded from static genesis authorities, the session keeps its current validators.
+//!
+//! As the `OnOffenceHandler` of `pallet_offences`, the pallet disables reported validators for
+//! the rest of the session and removes them from the set, as long as `MinAuthorities` allows.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use frame_support::{
+	traits::{Get, ValidatorRegistration},
+	weights::Weight,
+	BoundedVec,
+};
+use pallet_session::historical::IdentificationTuple;
+use sp_runtime::{traits::Convert, Perbill};
+use sp_staking::{
+	offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
+	SessionIndex,
+};
+use sp_std::prelude::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_session::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin allowed to add and remove validators.
+		type AddRemoveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The minimum number of validators.
+		#[pallet::constant]
+		type MinAuthorities: Get<u32>;
+
+		/// The maximum number of validators.
+		#[pallet::constant]
+		type MaxValidators: Get<u32>;
+
+		/// Tells whether a validator registered its session keys.
+		type ValidatorRegistration: ValidatorRegistration<Self::ValidatorId>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The validators for the next planned session.
+	#[pallet::storage]
+	#[pallet::getter(fn validators)]
+	pub type Validators<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The initial validators. They need session keys in the `pallet_session` genesis.
+		pub initial_validators: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { initial_validators: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let validators: BoundedVec<_, _> = self
+				.initial_validators
+				.clone()
+				.try_into()
+				.expect("initial validators must not exceed MaxValidators; qed");
+			Validators::<T>::put(validators);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A validator was added. It takes part from the session after next.
+		ValidatorAdded { validator: T::AccountId },
+		/// A validator was removed. It leaves after the next session.
+		ValidatorRemoved { validator: T::AccountId },
+		/// A validator was reported for an offence and disabled for the rest of the session.
+		ValidatorDisabled { validator: T::ValidatorId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already a validator.
+		AlreadyValidator,
+		/// The account is not a validator.
+		NotValidator,
+		/// The account has not registered its session keys.
+		NoSessionKeys,
+		/// Adding the validator would exceed `MaxValidators`.
+		TooManyValidators,
+		/// Removing the validator would go below `MinAuthorities`.
+		TooFewValidators,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Adds a validator. Its session keys must already be set.
+		#[pallet::weight(<T as Config>::WeightInfo::add_validator())]
+		pub fn add_validator(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			let validator_id =
+				T::ValidatorIdOf::convert(validator.clone()).ok_or(Error::<T>::NoSessionKeys)?;
+			ensure!(
+				T::ValidatorRegistration::is_registered(&validator_id),
+				Error::<T>::NoSessionKeys
+			);
+
+			Validators::<T>::try_mutate(|validators| {
+				ensure!(!validators.contains(&validator), Error::<T>::AlreadyValidator);
+				validators
+					.try_push(validator.clone())
+					.map_err(|_| Error::<T>::TooManyValidators)?;
+				Ok::<_, Error<T>>(())
+			})?;
+
+			Self::deposit_event(Event::ValidatorAdded { validator });
+			Ok(())
+		}
+
+		/// Removes a validator.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_validator())]
+		pub fn remove_validator(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			Validators::<T>::try_mutate(|validators| {
+				let index = validators
+					.iter()
+					.position(|v| *v == validator)
+					.ok_or(Error::<T>::NotValidator)?;
+				ensure!(
+					validators.len() as u32 > T::MinAuthorities::get(),
+					Error::<T>::TooFewValidators
+				);
+				validators.remove(index);
+				Ok::<_, Error<T>>(())
+			})?;
+
+			Self::deposit_event(Event::ValidatorRemoved { validator });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> pallet_session::SessionManager<T::ValidatorId> for Pallet<T> {
+	fn new_session(_new_index: u32) -> Option<Vec<T::ValidatorId>> {
+		let validators = Self::validators();
+		if validators.is_empty() {
+			return None
+		}
+		Some(validators.into_iter().filter_map(T::ValidatorIdOf::convert).collect())
+	}
+
+	fn end_session(_end_index: u32) {}
+
+	fn start_session(_start_index: u32) {}
+}
+
+impl<T> pallet_session::historical::SessionManager<T::ValidatorId, T::FullIdentification>
+	for Pallet<T>
+where
+	T: Config + pallet_session::historical::Config,
+{
+	fn new_session(new_index: u32) -> Option<Vec<(T::ValidatorId, T::FullIdentification)>> {
+		<Self as pallet_session::SessionManager<_>>::new_session(new_index).map(|validators| {
+			validators
+				.into_iter()
+				.filter_map(|v| T::FullIdentificationOf::convert(v.clone()).map(|full| (v, full)))
+				.collect()
+		})
+	}
+
+	fn end_session(_end_index: u32) {}
+
+	fn start_session(_start_index: u32) {}
+}
+
+impl<T> OnOffenceHandler<T::AccountId, IdentificationTuple<T>, Weight> for Pallet<T>
+where
+	T: Config + pallet_session::historical::Config,
+{
+	fn on_offence(
+		offenders: &[OffenceDetails<T::AccountId, IdentificationTuple<T>>],
+		_slash_fraction: &[Perbill],
+		_session: SessionIndex,
+		disable_strategy: DisableStrategy,
+	) -> Weight {
+		let db_weight = <T as frame_system::Config>::DbWeight::get();
+		let mut weight: Weight = 0;
+
+		for details in offenders {
+			let (validator_id, _) = &details.offender;
+
+			if disable_strategy != DisableStrategy::Never &&
+				pallet_session::Pallet::<T>::disable(validator_id)
+			{
+				Self::deposit_event(Event::ValidatorDisabled { validator: validator_id.clone() });
+			}
+
+			let removed = Validators::<T>::mutate(|validators| {
+				if validators.len() as u32 <= T::MinAuthorities::get() {
+					return None
+				}
+				let index = validators.iter().position(|v| {
+					T::ValidatorIdOf::convert(v.clone()).as_ref() == Some(validator_id)
+				})?;
+				Some(validators.remove(index))
+			});
+			if let Some(validator) = removed {
+				Self::deposit_event(Event::ValidatorRemoved { validator });
+			}
+
+			weight = weight.saturating_add(db_weight.reads_writes(3, 3));
+		}
+
+		weight
+	}
+}
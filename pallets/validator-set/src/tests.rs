@@ -0,0 +1,106 @@
+//! Unit tests for the Solar Validator Set pallet.
+
+use crate::{mock::*, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{DispatchError, Perbill};
+use sp_staking::offence::{DisableStrategy, OffenceDetails, OnOffenceHandler};
+
+#[test]
+fn genesis_validators_are_session_validators() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2, 3]);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+	});
+}
+
+#[test]
+fn add_validator_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		System::assert_last_event(Event::<Test>::ValidatorAdded { validator: 4 }.into());
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2, 3, 4]);
+
+		// Queued at the next session boundary, active at the one after.
+		rotate_sessions(1);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+		rotate_sessions(1);
+		assert_eq!(Session::validators(), vec![1, 2, 3, 4]);
+	});
+}
+
+#[test]
+fn add_validator_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::add_validator(Origin::signed(1), 4), DispatchError::BadOrigin);
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 1),
+			Error::<Test>::AlreadyValidator
+		);
+		assert_noop!(ValidatorSet::add_validator(Origin::root(), 6), Error::<Test>::NoSessionKeys);
+
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 5),
+			Error::<Test>::TooManyValidators
+		);
+	});
+}
+
+#[test]
+fn remove_validator_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 2));
+		System::assert_last_event(Event::<Test>::ValidatorRemoved { validator: 2 }.into());
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 3]);
+
+		rotate_sessions(2);
+		assert_eq!(Session::validators(), vec![1, 3]);
+	});
+}
+
+#[test]
+fn remove_validator_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::signed(1), 2),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 4),
+			Error::<Test>::NotValidator
+		);
+
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 2));
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 3),
+			Error::<Test>::TooFewValidators
+		);
+	});
+}
+
+fn report(validator: u64) {
+	let offenders = [OffenceDetails { offender: (validator, validator), reporters: vec![] }];
+	let weight = <ValidatorSet as OnOffenceHandler<_, _, _>>::on_offence(
+		&offenders,
+		&[Perbill::zero()],
+		Session::current_index(),
+		DisableStrategy::WhenSlashed,
+	);
+	assert!(weight > 0);
+}
+
+#[test]
+fn offenders_are_disabled_and_removed() {
+	new_test_ext().execute_with(|| {
+		report(2);
+		assert_eq!(Session::disabled_validators(), vec![1]);
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 3]);
+		System::assert_has_event(Event::<Test>::ValidatorDisabled { validator: 2 }.into());
+		System::assert_last_event(Event::<Test>::ValidatorRemoved { validator: 2 }.into());
+
+		// At `MinAuthorities` offenders are only disabled.
+		report(3);
+		assert_eq!(Session::disabled_validators(), vec![1, 2]);
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 3]);
+	});
+}
@@ -0,0 +1,130 @@
+//! Solar chain extension.
+//!
+//! Lets ink! contracts query and transfer native balances, query asset balances, check NFT
+//! ownership and whether an account is a verified creator without calling into a separate FT/NFT
+//! contract. The matching ink! environment lives in `contracts/solar_env`; function IDs and status
+//! codes here must stay in sync with it.
+
+use codec::Encode;
+use frame_support::{traits::Get, weights::Weight};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use pallet_solar_market::VerifiedCreators;
+use pallet_solar_nft::{CollectionId, TokenId};
+use sp_runtime::DispatchError;
+
+use crate::{impls::VerifiedByRegistrars, AccountId, AssetId, Assets, Balance, Nft, Runtime};
+
+/// Functions exposed to contracts, identified by the `func_id` passed to
+/// `seal_call_chain_extension`.
+enum FuncId {
+	/// `free_balance(account: AccountId) -> Balance`
+	FreeBalance,
+	/// `account_balance(account: AccountId) -> AccountData<Balance>`
+	AccountBalance,
+	/// `transfer(to: AccountId, value: Balance)`, from the calling contract.
+	Transfer,
+	/// `asset_balance(asset_id: AssetId, account: AccountId) -> Balance`
+	AssetBalance,
+	/// `nft_owner_of(collection_id: CollectionId, token_id: TokenId) -> Option<AccountId>`
+	NftOwnerOf,
+	/// `nft_balance_of(account: AccountId) -> u64`
+	NftBalanceOf,
+	/// `is_verified_creator(account: AccountId) -> bool`
+	IsVerifiedCreator,
+}
+
+impl TryFrom<u32> for FuncId {
+	type Error = DispatchError;
+
+	fn try_from(func_id: u32) -> Result<Self, Self::Error> {
+		match func_id {
+			1001 => Ok(Self::FreeBalance),
+			1002 => Ok(Self::AccountBalance),
+			1003 => Ok(Self::Transfer),
+			1004 => Ok(Self::AssetBalance),
+			1101 => Ok(Self::NftOwnerOf),
+			1102 => Ok(Self::NftBalanceOf),
+			1201 => Ok(Self::IsVerifiedCreator),
+			_ => Err(DispatchError::Other("Unimplemented func_id")),
+		}
+	}
+}
+
+/// Status code returned to the contract on success.
+const SUCCESS: u32 = 0;
+/// Status code returned to the contract when a transfer fails.
+const TRANSFER_FAILED: u32 = 1;
+
+/// The chain extension used by `pallet_contracts`.
+pub struct SolarExtension;
+
+impl ChainExtension<Runtime> for SolarExtension {
+	fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let mut env = env.buf_in_buf_out();
+		let read_weight: Weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+
+		match FuncId::try_from(func_id)? {
+			FuncId::FreeBalance => {
+				let account: AccountId = env.read_as()?;
+				env.charge_weight(read_weight)?;
+				let balance: Balance = pallet_balances::Pallet::<Runtime>::free_balance(&account);
+				env.write(&balance.encode(), false, None).map_err(write_error)?;
+			},
+			FuncId::AccountBalance => {
+				let account: AccountId = env.read_as()?;
+				env.charge_weight(read_weight)?;
+				let data = frame_system::Pallet::<Runtime>::account(&account).data;
+				env.write(&data.encode(), false, None).map_err(write_error)?;
+			},
+			FuncId::Transfer => {
+				let (to, value): (AccountId, Balance) = env.read_as()?;
+				env.charge_weight(
+					<Runtime as pallet_contracts::Config>::Schedule::get().host_fn_weights.transfer,
+				)?;
+				if env.ext().transfer(&to, value).is_err() {
+					return Ok(RetVal::Converging(TRANSFER_FAILED))
+				}
+			},
+			FuncId::AssetBalance => {
+				let (asset_id, account): (AssetId, AccountId) = env.read_as()?;
+				env.charge_weight(read_weight)?;
+				let balance: Balance = Assets::balance(asset_id, account);
+				env.write(&balance.encode(), false, None).map_err(write_error)?;
+			},
+			FuncId::NftOwnerOf => {
+				let (collection_id, token_id): (CollectionId, TokenId) = env.read_as()?;
+				env.charge_weight(read_weight)?;
+				let owner = Nft::owner_of(collection_id, token_id);
+				env.write(&owner.encode(), false, None).map_err(write_error)?;
+			},
+			FuncId::NftBalanceOf => {
+				let account: AccountId = env.read_as()?;
+				env.charge_weight(read_weight)?;
+				let balance = Nft::balance_of(account);
+				env.write(&balance.encode(), false, None).map_err(write_error)?;
+			},
+			FuncId::IsVerifiedCreator => {
+				let account: AccountId = env.read_as()?;
+				env.charge_weight(read_weight)?;
+				let verified = VerifiedByRegistrars::is_verified(&account);
+				env.write(&verified.encode(), false, None).map_err(write_error)?;
+			},
+		}
+
+		Ok(RetVal::Converging(SUCCESS))
+	}
+
+	fn enabled() -> bool {
+		true
+	}
+}
+
+fn write_error(_: DispatchError) -> DispatchError {
+	DispatchError::Other("ChainExtension failed to write output")
+}
@@ -0,0 +1,104 @@
+//! Auxiliary `struct`s and `impl`s used by the runtime.
+
+use frame_support::{
+	traits::{
+		fungibles::{Balanced, CreditOf},
+		Currency, Imbalance, OnUnbalanced,
+	},
+	weights::{
+		constants::ExtrinsicBaseWeight, WeightToFeeCoefficient, WeightToFeeCoefficients,
+		WeightToFeePolynomial,
+	},
+};
+use pallet_asset_tx_payment::HandleCredit;
+use pallet_identity::Judgement;
+use pallet_solar_market::VerifiedCreators;
+use smallvec::smallvec;
+use sp_runtime::Perbill;
+
+use crate::{AccountId, Assets, Authorship, Balance, Balances, Identity, Treasury, MILLIUNIT};
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Splits transaction fees charged in assets 80% to the treasury and 20% to the block author,
+/// like [`DealWithFees`].
+///
+/// `pallet_asset_tx_payment` hands over the fee and the tip as one credit, so unlike native tips,
+/// asset tips are split too. A share is burned when the author is unknown, or when it is below the
+/// asset's `min_balance` and the receiving account holds none of the asset.
+pub struct DealWithAssetFees;
+
+impl HandleCredit<AccountId, Assets> for DealWithAssetFees {
+	fn handle_credit(credit: CreditOf<AccountId, Assets>) {
+		let to_treasury = Perbill::from_percent(80) * credit.peek();
+		let (to_treasury, to_author) = credit.split(to_treasury);
+		// Drop the results which will trigger the `OnDrop` of the imbalances in case of error.
+		let _ = <Assets as Balanced<AccountId>>::resolve(&Treasury::account_id(), to_treasury);
+		if let Some(author) = Authorship::author() {
+			let _ = <Assets as Balanced<AccountId>>::resolve(&author, to_author);
+		}
+	}
+}
+
+/// Pays native transaction fees and tips to the block author.
+///
+/// The fee is burned when the author is unknown.
+pub struct ToAuthor;
+
+impl OnUnbalanced<NegativeImbalance> for ToAuthor {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		if let Some(author) = Authorship::author() {
+			Balances::resolve_creating(&author, amount);
+		}
+	}
+}
+
+/// Splits native transaction fees 80% to the treasury and 20% to the block author, and pays
+/// tips to the author in full.
+pub struct DealWithFees;
+
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+		if let Some(fees) = fees_then_tips.next() {
+			let (to_treasury, mut to_author) = fees.ration(80, 20);
+			if let Some(tips) = fees_then_tips.next() {
+				tips.merge_into(&mut to_author);
+			}
+			Treasury::on_unbalanced(to_treasury);
+			ToAuthor::on_unbalanced(to_author);
+		}
+	}
+}
+
+/// Verified creators are accounts whose identity a registrar has judged as `Reasonable` or
+/// `KnownGood`.
+pub struct VerifiedByRegistrars;
+
+impl VerifiedCreators<AccountId> for VerifiedByRegistrars {
+	fn is_verified(who: &AccountId) -> bool {
+		Identity::identity(who).map_or(false, |registration| {
+			registration.judgements.iter().any(|(_, judgement)| {
+				matches!(judgement, Judgement::Reasonable | Judgement::KnownGood)
+			})
+		})
+	}
+}
+
+/// Converts weight to fee so that an extrinsic of `ExtrinsicBaseWeight` costs one `MILLIUNIT`,
+/// the existential deposit, and heavier ones cost proportionally more.
+pub struct WeightToFee;
+
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = Balance;
+
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		let p = MILLIUNIT;
+		let q = Balance::from(ExtrinsicBaseWeight::get());
+		smallvec![WeightToFeeCoefficient {
+			degree: 1,
+			negative: false,
+			coeff_frac: Perbill::from_rational(p % q, q),
+			coeff_integer: p / q,
+		}]
+	}
+}
@@ -6,19 +6,32 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+mod chain_extension;
+mod impls;
+pub mod migrations;
+
+use codec::{Decode, Encode, MaxEncodedLen};
 pub use frame_support::traits::EqualPrivilegeOnly;
-use frame_support::{traits::OnRuntimeUpgrade, weights::DispatchClass};
+use frame_support::{
+	traits::{EitherOfDiverse, InstanceFilter, OnRuntimeUpgrade},
+	weights::DispatchClass,
+	PalletId, RuntimeDebug,
+};
 use frame_system::limits::{BlockLength, BlockWeights};
 use pallet_contracts::{migration, weights::WeightInfo, DefaultContractAccessWeight};
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
+use pallet_session::historical as pallet_session_historical;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
+	traits::{
+		AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, IdentifyAccount, NumberFor,
+		OpaqueKeys, Verify,
+	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
@@ -33,7 +46,7 @@ pub use frame_support::{
 	traits::{ConstU32, KeyOwnerProofSystem, Randomness, StorageInfo},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
-		IdentityFee, Weight,
+		ConstantMultiplier, IdentityFee, Weight,
 	},
 	StorageValue,
 };
@@ -41,9 +54,10 @@ pub use frame_system::Call as SystemCall;
 use frame_system::EnsureRoot;
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
-use pallet_transaction_payment::CurrencyAdapter;
+use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
+use sp_runtime::{FixedPointNumber, Perquintill};
 pub use sp_runtime::{Perbill, Permill};
 
 /// An index to a block.
@@ -59,6 +73,9 @@ pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::Account
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of an asset in `pallet_assets`.
+pub type AssetId = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -100,10 +117,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 108,
+	spec_version: 135,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -181,7 +198,10 @@ parameter_types! {
 		.avg_block_initialization(AVERAGE_ON_INITIALIZE_RATIO)
 		.build_or_panic();
 
-	pub const SS58Prefix: u8 = 42;
+	/// The Solarchain address format, the generic Substrate prefix 42 of the SS58 registry until
+	/// a prefix of its own is registered. Accounts are stored by public key, so changing it only
+	/// changes how addresses are displayed.
+	pub const SS58Prefix: u16 = 42;
 }
 
 // Configure FRAME pallets to include in runtime.
@@ -231,7 +251,7 @@ impl frame_system::Config for Runtime {
 	type AccountData = pallet_balances::AccountData<Balance>;
 	/// Weight information for the extrinsics of this pallet.
 	type SystemWeightInfo = ();
-	/// This is used as an identifier of the chain. 42 is the generic substrate prefix.
+	/// The address format of the chain.
 	type SS58Prefix = SS58Prefix;
 	/// The set code logic, just the default since we're not a parachain.
 	type OnSetCode = ();
@@ -240,15 +260,57 @@ impl frame_system::Config for Runtime {
 
 impl pallet_aura::Config for Runtime {
 	type AuthorityId = AuraId;
-	type DisabledValidators = ();
+	type DisabledValidators = Session;
 	type MaxAuthorities = ConstU32<32>;
 }
 
+parameter_types! {
+	pub const SessionPeriod: BlockNumber = HOURS;
+	pub const SessionOffset: BlockNumber = 0;
+}
+
+impl pallet_session::Config for Runtime {
+	type Event = Event;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, ValidatorSet>;
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_session::historical::Config for Runtime {
+	type FullIdentification = AccountId;
+	type FullIdentificationOf = ConvertInto;
+}
+
+impl pallet_offences::Config for Runtime {
+	type Event = Event;
+	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
+	type OnOffenceHandler = ValidatorSet;
+}
+
+impl pallet_solar_validator_set::Config for Runtime {
+	type Event = Event;
+	type AddRemoveOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type MinAuthorities = ConstU32<1>;
+	type MaxValidators = ConstU32<32>;
+	type ValidatorRegistration = Session;
+	type WeightInfo = pallet_solar_validator_set::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// Equivocation reports older than this are rejected.
+	pub const ReportLongevity: u64 = 7 * DAYS as u64;
+}
+
 impl pallet_grandpa::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
 
-	type KeyOwnerProofSystem = ();
+	type KeyOwnerProofSystem = Historical;
 
 	type KeyOwnerProof =
 		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
@@ -258,7 +320,11 @@ impl pallet_grandpa::Config for Runtime {
 		GrandpaId,
 	)>>::IdentificationTuple;
 
-	type HandleEquivocation = ();
+	type HandleEquivocation = pallet_grandpa::EquivocationHandler<
+		Self::KeyOwnerIdentification,
+		Offences,
+		ReportLongevity,
+	>;
 
 	type WeightInfo = ();
 	type MaxAuthorities = ConstU32<32>;
@@ -269,14 +335,46 @@ parameter_types! {
 }
 
 impl pallet_authorship::Config for Runtime {
-	type FindAuthor = ();
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
 	type UncleGenerations = UncleGenerations;
 	type FilterUncle = ();
-	type EventHandler = ();
+	type EventHandler = BlockReward;
+}
+
+parameter_types! {
+	pub const BlocksPerYear: BlockNumber = 365 * DAYS;
+}
+
+impl pallet_solar_block_reward::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BlocksPerYear = BlocksPerYear;
+	type UpdateOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type MaxScheduleLen = ConstU32<16>;
+	type WeightInfo = pallet_solar_block_reward::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_randomness_collective_flip::Config for Runtime {}
 
+parameter_types! {
+	// Slashed when a secret is withheld, so it must outweigh what a biased draw could win.
+	pub const CommitDeposit: Balance = 100 * UNIT;
+	pub const RevealDelay: BlockNumber = 1;
+	pub const RevealWindow: BlockNumber = 10 * MINUTES;
+	pub const SeedDelay: BlockNumber = 3;
+}
+
+impl pallet_solar_randomness::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type CommitDeposit = CommitDeposit;
+	type RevealDelay = RevealDelay;
+	type RevealWindow = RevealWindow;
+	type SeedDelay = SeedDelay;
+	type Slash = Treasury;
+	type WeightInfo = pallet_solar_randomness::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
 }
@@ -291,6 +389,9 @@ impl pallet_timestamp::Config for Runtime {
 
 parameter_types! {
 	pub const ExistentialDeposit: u128 = EXISTENTIAL_DEPOSIT;
+	/// Locks are keyed by identifier, and only `Democracy` and `Vesting` take one (a single lock
+	/// each, however many votes or vesting schedules an account has), so 50 leaves ample room for
+	/// pallets added later. Going over the limit only logs a warning.
 	pub const MaxLocks: u32 = 50;
 }
 
@@ -309,15 +410,31 @@ impl pallet_balances::Config for Runtime {
 }
 
 parameter_types! {
+	pub const TransactionByteFee: Balance = MILLIUNIT / 100;
 	pub OperationalFeeMultiplier: u8 = 5;
+	/// Fees rise when blocks are fuller than this, and fall when they are emptier.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction = CurrencyAdapter<Balances, impls::DealWithFees>;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
-	type WeightToFee = IdentityFee<Balance>;
-	type LengthToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate = ();
+	type WeightToFee = impls::WeightToFee;
+	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
+	type FeeMultiplierUpdate =
+		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+}
+
+impl pallet_asset_tx_payment::Config for Runtime {
+	type Fungibles = Assets;
+	/// Fees paid in a sufficient asset are converted using the ratio of the asset's
+	/// `min_balance` to the `ExistentialDeposit`.
+	type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
+		pallet_assets::BalanceToAssetBalance<Balances, Runtime, ConvertInto>,
+		impls::DealWithAssetFees,
+	>;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -345,23 +462,24 @@ parameter_types! {
 
 impl pallet_contracts::Config for Runtime {
 	type Time = Timestamp;
-	type Randomness = RandomnessCollectiveFlip;
+	/// `seal_random` is backed by commit-reveal randomness, which block authors can not bias
+	/// within their block.
+	type Randomness = CommitReveal;
 	type Currency = Balances;
 	type Event = Event;
 	type Call = Call;
-	/// The safest default is to allow no calls at all.
+	/// Only dispatchables whitelisted in `ContractsFilter` can be called from contracts.
 	///
-	/// Runtimes should whitelist dispatchables that are allowed to be called from contracts
-	/// and make sure they are stable. Dispatchables exposed to contracts are not allowed to
-	/// change because that would break already deployed contracts. The `Call` structure itself
-	/// is not allowed to change the indices of existing pallets, too.
-	type CallFilter = frame_support::traits::Nothing;
+	/// Dispatchables exposed to contracts are not allowed to change because that would break
+	/// already deployed contracts. The `Call` structure itself is not allowed to change the
+	/// indices of existing pallets, too.
+	type CallFilter = pallet_solar_call_filter::Whitelist<Runtime>;
 	type DepositPerItem = DepositPerItem;
 	type DepositPerByte = DepositPerByte;
 	type CallStack = [pallet_contracts::Frame<Self>; 31];
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
-	type ChainExtension = ();
+	type ChainExtension = chain_extension::SolarExtension;
 	type DeletionQueueDepth = DeletionQueueDepth;
 	type DeletionWeightLimit = DeletionWeightLimit;
 	type Schedule = Schedule;
@@ -379,17 +497,43 @@ impl pallet_contracts::Config for Runtime {
 	type RelaxedMaxCodeLen = ConstU32<{ 512 * 1024 }>;
 }
 
+/// Makes the treasury the organiser of the NFT market, so trade commissions fund it.
+pub type CommissionToTreasury =
+	pallet_solar_market::migrations::v1::MoveOrganiser<Runtime, TreasuryAccount>;
+
 pub struct Migrations;
 impl OnRuntimeUpgrade for Migrations {
 	fn on_runtime_upgrade() -> Weight {
 		migration::migrate::<Runtime>()
+			.saturating_add(migrations::RetireSudo::on_runtime_upgrade())
+			.saturating_add(CommissionToTreasury::on_runtime_upgrade())
+			.saturating_add(migrations::SeedValidatorSet::on_runtime_upgrade())
 	}
 }
 
+parameter_types! {
+	pub const PreimageMaxSize: u32 = 4096 * 1024;
+	pub const PreimageBaseDeposit: Balance = deposit(2, 64);
+}
+
+impl pallet_preimage::Config for Runtime {
+	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+	type Event = Event;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type MaxSize = PreimageMaxSize;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+}
+
 // Define the types required by the Scheduler pallet.
 parameter_types! {
-	pub MaximumSchedulerWeight: Weight = 10_000_000;
+	// Runtime upgrades take a whole block, so scheduled calls may use most of it.
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
+		RuntimeBlockWeights::get().max_block;
 	pub const MaxScheduledPerBlock: u32 = 50;
+	// Retry a call scheduled by hash in 10 blocks if its preimage is still missing.
+	pub const NoPreimagePostponement: Option<BlockNumber> = Some(10);
 }
 
 // Configure the runtime's implementation of the Scheduler pallet.
@@ -399,12 +543,210 @@ impl pallet_scheduler::Config for Runtime {
 	type PalletsOrigin = OriginCaller;
 	type Call = Call;
 	type MaximumWeight = MaximumSchedulerWeight;
-	type ScheduleOrigin = frame_system::EnsureRoot<AccountId>;
+	type ScheduleOrigin = EnsureRootOrMoreThanHalfCouncil;
 	type MaxScheduledPerBlock = MaxScheduledPerBlock;
-	type WeightInfo = ();
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
 	type OriginPrivilegeCmp = EqualPrivilegeOnly;
-	type PreimageProvider = ();
-	type NoPreimagePostponement = ();
+	type PreimageProvider = Preimage;
+	type NoPreimagePostponement = NoPreimagePostponement;
+}
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+}
+
+type CouncilCollective = pallet_collective::Instance1;
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const TechnicalMotionDuration: BlockNumber = 3 * DAYS;
+	pub const TechnicalMaxProposals: u32 = 100;
+	pub const TechnicalMaxMembers: u32 = 100;
+}
+
+type TechnicalCollective = pallet_collective::Instance2;
+impl pallet_collective::Config<TechnicalCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = TechnicalMotionDuration;
+	type MaxProposals = TechnicalMaxProposals;
+	type MaxMembers = TechnicalMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// Root, or more than half of the council.
+pub type EnsureRootOrMoreThanHalfCouncil = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>,
+>;
+
+/// Root, or at least three quarters of the council.
+pub type EnsureRootOrThreeFourthsCouncil = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>,
+>;
+
+type CouncilMembershipInstance = pallet_membership::Instance1;
+impl pallet_membership::Config<CouncilMembershipInstance> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRootOrThreeFourthsCouncil;
+	type RemoveOrigin = EnsureRootOrThreeFourthsCouncil;
+	type SwapOrigin = EnsureRootOrThreeFourthsCouncil;
+	type ResetOrigin = EnsureRootOrThreeFourthsCouncil;
+	type PrimeOrigin = EnsureRootOrThreeFourthsCouncil;
+	type MembershipInitialized = Council;
+	type MembershipChanged = Council;
+	type MaxMembers = CouncilMaxMembers;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+type TechnicalMembershipInstance = pallet_membership::Instance2;
+impl pallet_membership::Config<TechnicalMembershipInstance> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type RemoveOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type SwapOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type ResetOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type PrimeOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type MembershipInitialized = TechnicalCommittee;
+	type MembershipChanged = TechnicalCommittee;
+	type MaxMembers = TechnicalMaxMembers;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 7 * DAYS;
+	pub const VotingPeriod: BlockNumber = 7 * DAYS;
+	pub const FastTrackVotingPeriod: BlockNumber = 3 * HOURS;
+	pub const EnactmentPeriod: BlockNumber = 2 * DAYS;
+	pub const CooloffPeriod: BlockNumber = 7 * DAYS;
+	pub const MinimumDeposit: Balance = 100 * UNIT;
+	pub const PreimageByteDeposit: Balance = deposit(0, 1);
+	pub const MaxVotes: u32 = 100;
+	pub const MaxProposals: u32 = 100;
+}
+
+impl pallet_democracy::Config for Runtime {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = Balances;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type VoteLockingPeriod = EnactmentPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	/// A straight majority of the council can decide what their next motion is.
+	type ExternalOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 2>;
+	/// A super-majority can have the next scheduled referendum be a straight majority-carries vote.
+	type ExternalMajorityOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>;
+	/// A unanimous council can have the next scheduled referendum be a straight default-carries
+	/// (NTB) vote.
+	type ExternalDefaultOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 1>;
+	/// Two thirds of the technical committee can have an ExternalMajority/ExternalDefault vote
+	/// be tabled immediately and with a shorter voting/enactment period.
+	type FastTrackOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 2, 3>;
+	type InstantOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 1>;
+	type InstantAllowed = frame_support::traits::ConstBool<true>;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	/// To cancel a proposal which has been passed, 2/3 of the council must agree to it.
+	type CancellationOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+	/// To cancel a proposal before it has been passed, the technical committee must be unanimous
+	/// or Root must agree.
+	type CancelProposalOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 1>,
+	>;
+	type BlacklistOrigin = EnsureRoot<AccountId>;
+	/// Any single technical committee member may veto a coming council proposal, however they
+	/// can only do it once and it lasts only for the cool-off period.
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, TechnicalCollective>;
+	type CooloffPeriod = CooloffPeriod;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type OperationalPreimageOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type Slash = Treasury;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
+	type MaxProposals = MaxProposals;
+}
+
+parameter_types! {
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = UNIT;
+	pub const SpendPeriod: BlockNumber = DAYS;
+	pub const Burn: Permill = Permill::from_percent(1);
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxApprovals: u32 = 100;
+	pub TreasuryAccount: AccountId = Treasury::account_id();
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	/// Spend proposals are approved by three fifths of the council.
+	type ApproveOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 5>,
+	>;
+	type RejectOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type Event = Event;
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ();
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = Bounties;
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = MaxApprovals;
+}
+
+parameter_types! {
+	pub const BountyDepositBase: Balance = UNIT;
+	pub const BountyDepositPayoutDelay: BlockNumber = DAYS;
+	pub const BountyUpdatePeriod: BlockNumber = 14 * DAYS;
+	pub const CuratorDepositMultiplier: Permill = Permill::from_percent(50);
+	pub const CuratorDepositMin: Balance = UNIT;
+	pub const CuratorDepositMax: Balance = 100 * UNIT;
+	pub const BountyValueMinimum: Balance = 5 * UNIT;
+	pub const DataDepositPerByte: Balance = deposit(0, 1);
+	pub const MaximumReasonLength: u32 = 300;
+}
+
+impl pallet_bounties::Config for Runtime {
+	type Event = Event;
+	type BountyDepositBase = BountyDepositBase;
+	type BountyDepositPayoutDelay = BountyDepositPayoutDelay;
+	type BountyUpdatePeriod = BountyUpdatePeriod;
+	type CuratorDepositMultiplier = CuratorDepositMultiplier;
+	type CuratorDepositMin = CuratorDepositMin;
+	type CuratorDepositMax = CuratorDepositMax;
+	type BountyValueMinimum = BountyValueMinimum;
+	type DataDepositPerByte = DataDepositPerByte;
+	type MaximumReasonLength = MaximumReasonLength;
+	type WeightInfo = pallet_bounties::weights::SubstrateWeight<Runtime>;
+	type ChildBountyManager = ();
 }
 
 parameter_types! {
@@ -416,13 +758,264 @@ impl pallet_node_authorization::Config for Runtime {
 	type Event = Event;
 	type MaxWellKnownNodes = MaxWellKnownNodes;
 	type MaxPeerIdLength = MaxPeerIdLength;
-	type AddOrigin = EnsureRoot<AccountId>;
-	type RemoveOrigin = EnsureRoot<AccountId>;
-	type SwapOrigin = EnsureRoot<AccountId>;
-	type ResetOrigin = EnsureRoot<AccountId>;
+	type AddOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type RemoveOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type SwapOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type ResetOrigin = EnsureRootOrMoreThanHalfCouncil;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const NftCollectionDeposit: Balance = 10 * UNIT;
+	pub const NftItemDeposit: Balance = deposit(1, 256);
+	pub const NftStringLimit: u32 = 256;
+}
+
+impl pallet_solar_nft::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type CollectionDeposit = NftCollectionDeposit;
+	type ItemDeposit = NftItemDeposit;
+	type StringLimit = NftStringLimit;
+	type WeightInfo = pallet_solar_nft::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const MarketPalletId: PalletId = PalletId(*b"sol/mrkt");
+}
+
+impl pallet_solar_market::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Nfts = Nft;
+	type VerifiedCreators = impls::VerifiedByRegistrars;
+	type PalletId = MarketPalletId;
+	type AdminOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type WeightInfo = pallet_solar_market::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_utility::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
+	pub const DepositBase: Balance = deposit(1, 88);
+	// Additional storage item size of 32 bytes.
+	pub const DepositFactor: Balance = deposit(0, 32);
+	pub const MaxSignatories: u16 = 100;
+}
+
+impl pallet_multisig::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type DepositBase = DepositBase;
+	type DepositFactor = DepositFactor;
+	type MaxSignatories = MaxSignatories;
+	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// One storage item; key size 32, value size 8.
+	pub const ProxyDepositBase: Balance = deposit(1, 8);
+	// Additional storage item size of 33 bytes.
+	pub const ProxyDepositFactor: Balance = deposit(0, 33);
+	pub const AnnouncementDepositBase: Balance = deposit(1, 8);
+	pub const AnnouncementDepositFactor: Balance = deposit(0, 66);
+}
+
+/// The type used to represent the kinds of proxying allowed.
+///
+/// New variants must be appended, the encoded index of existing ones must not change.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	MaxEncodedLen,
+	scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+	/// Any call.
+	Any,
+	/// Governance calls, to vote and propose on behalf of a council member.
+	Governance,
+	/// Contract deployment and calls only, such as mass mints through a contract.
+	Contracts,
+	/// Native NFT and marketplace calls only.
+	Nft,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::Governance => matches!(
+				c,
+				Call::Council(..) |
+					Call::TechnicalCommittee(..) |
+					Call::Democracy(..) |
+					Call::Treasury(..) |
+					Call::Bounties(..) |
+					Call::Utility(..)
+			),
+			// Calls batched with `Utility` are checked against the proxy type one by one.
+			ProxyType::Contracts => matches!(c, Call::Contracts(..) | Call::Utility(..)),
+			ProxyType::Nft => matches!(c, Call::Nft(..) | Call::Market(..) | Call::Utility(..)),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			_ => false,
+		}
+	}
+}
+
+impl pallet_proxy::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = ConstU32<32>;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = ConstU32<32>;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+parameter_types! {
+	// 258 bytes on-chain.
+	pub const BasicDeposit: Balance = deposit(1, 258);
+	// 66 bytes on-chain.
+	pub const FieldDeposit: Balance = deposit(0, 66);
+	// 53 bytes on-chain.
+	pub const SubAccountDeposit: Balance = deposit(1, 53);
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+}
+
+/// Registrars added by the council give `Reasonable` or `KnownGood` judgements to NFT authors,
+/// which makes them verified creators for the market and `SolarExtension::is_verified_creator`.
+impl pallet_identity::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BasicDeposit = BasicDeposit;
+	type FieldDeposit = FieldDeposit;
+	type SubAccountDeposit = SubAccountDeposit;
+	type MaxSubAccounts = MaxSubAccounts;
+	type MaxAdditionalFields = MaxAdditionalFields;
+	type MaxRegistrars = MaxRegistrars;
+	type Slashed = Treasury;
+	type ForceOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type RegistrarOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const MinVestedTransfer: Balance = UNIT;
+}
+
+/// Team and creator allocations are locked at genesis and released linearly per block.
+impl pallet_vesting::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+	// `VestingInfo` encode length is 36 bytes. 28 schedules gets encoded as 1009 bytes, which is
+	// the highest number of schedules that encodes less than 2^10.
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+parameter_types! {
+	/// Calls contracts may dispatch through `seal_call_runtime` when `ContractsFilter` is
+	/// introduced. Governance changes the list afterwards with `allow_call`/`disallow_call`.
+	/// Scheduler calls still have to pass `ScheduleOrigin`.
+	pub ContractsCallWhitelist: Vec<(&'static str, &'static str)> = vec![
+		("Balances", "transfer"),
+		("Balances", "transfer_keep_alive"),
+		("Scheduler", "schedule"),
+		("Scheduler", "cancel"),
+		("Scheduler", "schedule_named"),
+		("Scheduler", "cancel_named"),
+		("Scheduler", "schedule_after"),
+		("Scheduler", "schedule_named_after"),
+		("Nft", "create_collection"),
+		("Nft", "mint"),
+		("Nft", "burn"),
+		("Nft", "transfer"),
+		("Nft", "transfer_from"),
+		("Nft", "approve"),
+		("Nft", "set_metadata"),
+		("Market", "offer"),
+		("Market", "cancel_offer"),
+		("Market", "buy"),
+	];
+}
+
+impl pallet_solar_call_filter::Config for Runtime {
+	type Event = Event;
+	type ManagerOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type MaxNameLen = ConstU32<64>;
+	type InitialWhitelist = ContractsCallWhitelist;
+	type WeightInfo = pallet_solar_call_filter::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 10 * UNIT;
+	pub const AssetsStringLimit: u32 = 50;
+	/// Key is 4 byte asset ID, value is the `AssetMetadata` of 68 bytes with a 50 byte limit.
+	pub const MetadataDepositBase: Balance = deposit(1, 68);
+	pub const MetadataDepositPerByte: Balance = deposit(0, 1);
+	pub const ApprovalDeposit: Balance = EXISTENTIAL_DEPOSIT;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = Call;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -442,6 +1035,32 @@ construct_runtime!(
 		Contracts: pallet_contracts,
 		Scheduler: pallet_scheduler,
 		NodeAuthorization: pallet_node_authorization,
+		Nft: pallet_solar_nft,
+		Market: pallet_solar_market,
+		ContractsFilter: pallet_solar_call_filter,
+		Assets: pallet_assets,
+		AssetTxPayment: pallet_asset_tx_payment,
+		Council: pallet_collective::<Instance1>,
+		CouncilMembership: pallet_membership::<Instance1>,
+		TechnicalCommittee: pallet_collective::<Instance2>,
+		TechnicalMembership: pallet_membership::<Instance2>,
+		Democracy: pallet_democracy,
+		// `ValidatorSet` must come before `Session`, which reads the initial validators from it
+		// at genesis.
+		ValidatorSet: pallet_solar_validator_set,
+		Session: pallet_session,
+		Historical: pallet_session_historical::{Pallet},
+		Offences: pallet_offences,
+		CommitReveal: pallet_solar_randomness,
+		Preimage: pallet_preimage,
+		Treasury: pallet_treasury,
+		Bounties: pallet_bounties,
+		BlockReward: pallet_solar_block_reward,
+		Utility: pallet_utility,
+		Multisig: pallet_multisig,
+		Proxy: pallet_proxy,
+		Identity: pallet_identity,
+		Vesting: pallet_vesting,
 	}
 );
 
@@ -460,7 +1079,7 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
 );
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
@@ -567,23 +1186,27 @@ impl_runtime_apis! {
 		}
 
 		fn submit_report_equivocation_unsigned_extrinsic(
-			_equivocation_proof: fg_primitives::EquivocationProof<
+			equivocation_proof: fg_primitives::EquivocationProof<
 				<Block as BlockT>::Hash,
 				NumberFor<Block>,
 			>,
-			_key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
+			key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
 		) -> Option<()> {
-			None
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Grandpa::submit_unsigned_equivocation_report(
+				equivocation_proof,
+				key_owner_proof,
+			)
 		}
 
 		fn generate_key_ownership_proof(
 			_set_id: fg_primitives::SetId,
-			_authority_id: GrandpaId,
+			authority_id: GrandpaId,
 		) -> Option<fg_primitives::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
+			Historical::prove((fg_primitives::KEY_TYPE, authority_id))
+				.map(|p| p.encode())
+				.map(fg_primitives::OpaqueKeyOwnershipProof::new)
 		}
 	}
 
@@ -609,6 +1232,57 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_solar_block_reward_rpc_runtime_api::BlockRewardApi<Block, AccountId, Balance>
+		for Runtime
+	{
+		fn next_block_reward() -> Balance {
+			BlockReward::block_reward(System::block_number() + 1)
+		}
+
+		fn total_rewards(who: AccountId) -> Balance {
+			BlockReward::total_rewards(who)
+		}
+	}
+
+	impl pallet_solar_nft_rpc_runtime_api::NftApi<Block, AccountId> for Runtime {
+		fn owner_of(
+			collection_id: pallet_solar_nft::CollectionId,
+			token_id: pallet_solar_nft::TokenId,
+		) -> Option<AccountId> {
+			Nft::owner_of(collection_id, token_id)
+		}
+
+		fn tokens_of(
+			account: AccountId,
+			start: u32,
+			limit: u32,
+		) -> Vec<(pallet_solar_nft::CollectionId, pallet_solar_nft::TokenId)> {
+			Nft::tokens_of(&account, start, limit)
+		}
+
+		fn token_info(
+			collection_id: pallet_solar_nft::CollectionId,
+			token_id: pallet_solar_nft::TokenId,
+		) -> Option<pallet_solar_nft::TokenInfo<AccountId, Vec<u8>>> {
+			Nft::token(collection_id, token_id).map(|token| pallet_solar_nft::TokenInfo {
+				owner: token.owner,
+				approval: token.approval,
+				metadata: token.metadata.map(Into::into),
+			})
+		}
+
+		fn collection_info(
+			collection_id: pallet_solar_nft::CollectionId,
+		) -> Option<pallet_solar_nft::CollectionInfo<AccountId, Vec<u8>>> {
+			Nft::collection(collection_id).map(|collection| pallet_solar_nft::CollectionInfo {
+				owner: collection.owner,
+				name: collection.name.into(),
+				symbol: collection.symbol.into(),
+				total_supply: collection.total_supply,
+			})
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -619,13 +1293,41 @@ impl_runtime_apis! {
 			use frame_support::traits::StorageInfoTrait;
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use baseline::Pallet as BaselineBench;
+			use pallet_solar_node_authorization_benchmarking::Pallet as NodeAuthorizationBench;
 
+			// Every pallet with benchmarks is listed here, once per instance so each gets its own
+			// weights module. `pallet_session` and `pallet_offences` are missing because their benchmarks
+			// need `pallet_staking`; the other pallets have none.
 			let mut list = Vec::<BenchmarkList>::new();
 
 			list_benchmark!(list, extra, frame_benchmarking, BaselineBench::<Runtime>);
 			list_benchmark!(list, extra, frame_system, SystemBench::<Runtime>);
 			list_benchmark!(list, extra, pallet_balances, Balances);
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
+			list_benchmark!(list, extra, pallet_grandpa, Grandpa);
+			list_benchmark!(list, extra, pallet_contracts, Contracts);
+			list_benchmark!(list, extra, pallet_scheduler, Scheduler);
+			list_benchmark!(list, extra, pallet_preimage, Preimage);
+			list_benchmark!(list, extra, pallet_node_authorization, NodeAuthorizationBench::<Runtime>);
+			list_benchmark!(list, extra, pallet_solar_nft, Nft);
+			list_benchmark!(list, extra, pallet_solar_market, Market);
+			list_benchmark!(list, extra, pallet_solar_call_filter, ContractsFilter);
+			list_benchmark!(list, extra, pallet_assets, Assets);
+			list_benchmark!(list, extra, pallet_collective_council, Council);
+			list_benchmark!(list, extra, pallet_collective_technical_committee, TechnicalCommittee);
+			list_benchmark!(list, extra, pallet_membership_council, CouncilMembership);
+			list_benchmark!(list, extra, pallet_membership_technical_committee, TechnicalMembership);
+			list_benchmark!(list, extra, pallet_democracy, Democracy);
+			list_benchmark!(list, extra, pallet_solar_validator_set, ValidatorSet);
+			list_benchmark!(list, extra, pallet_solar_randomness, CommitReveal);
+			list_benchmark!(list, extra, pallet_treasury, Treasury);
+			list_benchmark!(list, extra, pallet_bounties, Bounties);
+			list_benchmark!(list, extra, pallet_solar_block_reward, BlockReward);
+			list_benchmark!(list, extra, pallet_utility, Utility);
+			list_benchmark!(list, extra, pallet_multisig, Multisig);
+			list_benchmark!(list, extra, pallet_proxy, Proxy);
+			list_benchmark!(list, extra, pallet_identity, Identity);
+			list_benchmark!(list, extra, pallet_vesting, Vesting);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -639,9 +1341,11 @@ impl_runtime_apis! {
 
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use baseline::Pallet as BaselineBench;
+			use pallet_solar_node_authorization_benchmarking::Pallet as NodeAuthorizationBench;
 
 			impl frame_system_benchmarking::Config for Runtime {}
 			impl baseline::Config for Runtime {}
+			impl pallet_solar_node_authorization_benchmarking::Config for Runtime {}
 
 			let whitelist: Vec<TrackedStorageKey> = vec![
 				// Block Number
@@ -663,6 +1367,30 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_grandpa, Grandpa);
+			add_benchmark!(params, batches, pallet_contracts, Contracts);
+			add_benchmark!(params, batches, pallet_scheduler, Scheduler);
+			add_benchmark!(params, batches, pallet_preimage, Preimage);
+			add_benchmark!(params, batches, pallet_node_authorization, NodeAuthorizationBench::<Runtime>);
+			add_benchmark!(params, batches, pallet_solar_nft, Nft);
+			add_benchmark!(params, batches, pallet_solar_market, Market);
+			add_benchmark!(params, batches, pallet_solar_call_filter, ContractsFilter);
+			add_benchmark!(params, batches, pallet_assets, Assets);
+			add_benchmark!(params, batches, pallet_collective_council, Council);
+			add_benchmark!(params, batches, pallet_collective_technical_committee, TechnicalCommittee);
+			add_benchmark!(params, batches, pallet_membership_council, CouncilMembership);
+			add_benchmark!(params, batches, pallet_membership_technical_committee, TechnicalMembership);
+			add_benchmark!(params, batches, pallet_democracy, Democracy);
+			add_benchmark!(params, batches, pallet_solar_validator_set, ValidatorSet);
+			add_benchmark!(params, batches, pallet_solar_randomness, CommitReveal);
+			add_benchmark!(params, batches, pallet_treasury, Treasury);
+			add_benchmark!(params, batches, pallet_bounties, Bounties);
+			add_benchmark!(params, batches, pallet_solar_block_reward, BlockReward);
+			add_benchmark!(params, batches, pallet_utility, Utility);
+			add_benchmark!(params, batches, pallet_multisig, Multisig);
+			add_benchmark!(params, batches, pallet_proxy, Proxy);
+			add_benchmark!(params, batches, pallet_identity, Identity);
+			add_benchmark!(params, batches, pallet_vesting, Vesting);
 
 			Ok(batches)
 		}
@@ -0,0 +1,110 @@
+//! Runtime storage migrations run by [`crate::Migrations`].
+
+use frame_support::{
+	storage::migration::take_storage_value,
+	traits::{Get, OnRuntimeUpgrade, PalletInfoAccess},
+	weights::Weight,
+	BoundedVec,
+};
+use sp_runtime::traits::OpaqueKeys;
+use sp_std::prelude::*;
+
+use crate::{
+	opaque::SessionKeys, AccountId, Aura, Council, Grandpa, Runtime, Session, Sudo, System,
+	ValidatorSet,
+};
+
+/// Removes the sudo key once the council can take over.
+///
+/// `pallet_sudo` stays in the runtime so the indices of later pallets in `Call` do not change,
+/// but without a key every sudo call fails with `RequireSudo`. The key is kept while the council
+/// has no members, so the chain is never left without a governing origin.
+pub struct RetireSudo;
+
+impl OnRuntimeUpgrade for RetireSudo {
+	fn on_runtime_upgrade() -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+
+		if Sudo::key().is_none() {
+			return db_weight.reads(1)
+		}
+		if Council::members().is_empty() {
+			log::warn!(target: "runtime::sudo", "council has no members, keeping the sudo key");
+			return db_weight.reads(2)
+		}
+
+		let key = take_storage_value::<AccountId>(Sudo::name().as_bytes(), b"Key", &[]);
+		log::info!(target: "runtime::sudo", "retired sudo key {:?}", key);
+		db_weight.reads_writes(2, 1)
+	}
+}
+
+/// Hands the static Aura and GRANDPA authorities of a chain started without `pallet_session` to
+/// the session and `pallet_solar_validator_set`.
+///
+/// Without it the first session change would find no queued keys and leave Aura and GRANDPA
+/// without authorities. The i-th Aura and GRANDPA authorities belong to the same validator, as in
+/// the chain spec, and the validator account is the one of its Aura key. Runs while both the
+/// session and the validator set are empty, so chains started with them are left alone.
+pub struct SeedValidatorSet;
+
+impl OnRuntimeUpgrade for SeedValidatorSet {
+	fn on_runtime_upgrade() -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+
+		if !Session::validators().is_empty() || !ValidatorSet::validators().is_empty() {
+			return db_weight.reads(2)
+		}
+		let aura = Aura::authorities();
+		let grandpa = Grandpa::grandpa_authorities();
+		if aura.is_empty() || aura.len() != grandpa.len() {
+			log::warn!(
+				target: "runtime::validator-set",
+				"{} Aura and {} GRANDPA authorities, not seeding the validator set",
+				aura.len(),
+				grandpa.len(),
+			);
+			return db_weight.reads(4)
+		}
+
+		let validators: Vec<(AccountId, SessionKeys)> = aura
+			.into_iter()
+			.zip(grandpa.into_iter().map(|(grandpa, _)| grandpa))
+			.map(|(aura, grandpa)| {
+				let raw: &[u8] = aura.as_ref();
+				let account = AccountId::try_from(raw)
+					.expect("Aura keys are sr25519 public keys of 32 bytes; qed");
+				(account, SessionKeys { aura, grandpa })
+			})
+			.collect();
+		let accounts: Vec<AccountId> =
+			validators.iter().map(|(account, _)| account.clone()).collect();
+		let bounded: BoundedVec<_, _> = match accounts.clone().try_into() {
+			Ok(bounded) => bounded,
+			Err(_) => {
+				log::warn!(target: "runtime::validator-set", "too many authorities to seed");
+				return db_weight.reads(4)
+			},
+		};
+
+		// Like the genesis build of `pallet_session`, which this chain never ran.
+		for (account, keys) in &validators {
+			if !System::account_exists(account) {
+				System::inc_providers(account);
+			}
+			let _ = System::inc_consumers(account);
+			for id in SessionKeys::key_ids() {
+				let key = keys.get_raw(*id).to_vec();
+				pallet_session::KeyOwner::<Runtime>::insert((*id, key), account);
+			}
+			pallet_session::NextKeys::<Runtime>::insert(account, keys);
+		}
+		pallet_session::Validators::<Runtime>::put(&accounts);
+		pallet_session::QueuedKeys::<Runtime>::put(&validators);
+		pallet_solar_validator_set::Validators::<Runtime>::put(bounded);
+
+		log::info!(target: "runtime::validator-set", "seeded {} validators", accounts.len());
+		let count = validators.len() as Weight;
+		db_weight.reads_writes(4 + count, 3 + 5 * count)
+	}
+}
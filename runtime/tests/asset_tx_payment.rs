@@ -0,0 +1,98 @@
+//! Transaction fees can be paid in a sufficient asset instead of the native token.
+
+mod common;
+
+use common::{new_test_ext, ALICE, STABLE, UNIT};
+use frame_support::{
+	assert_ok,
+	weights::{GetDispatchInfo, Pays, PostDispatchInfo},
+};
+use pallet_asset_tx_payment::ChargeAssetTxPayment;
+use solar_node_runtime::{Assets, Balances, Call, Origin, Runtime, TransactionPayment, Treasury};
+use sp_runtime::{traits::SignedExtension, Perbill};
+
+const LEN: usize = 100;
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![0u8; 32] })
+}
+
+#[test]
+fn fee_is_paid_in_asset() {
+	new_test_ext().execute_with(|| {
+		let call = remark();
+		let info = call.get_dispatch_info();
+		let fee = TransactionPayment::compute_fee(LEN as u32, &info, 0);
+		assert!(fee > 0);
+
+		let pre = ChargeAssetTxPayment::<Runtime>::from(0, Some(STABLE))
+			.pre_dispatch(&ALICE, &call, &info, LEN)
+			.unwrap();
+		// `min_balance` equals the existential deposit, so the asset is priced one-to-one.
+		assert_eq!(Assets::balance(STABLE, ALICE), 1_000 * UNIT - fee);
+		assert_eq!(Balances::free_balance(&ALICE), 1_000 * UNIT);
+
+		let post_info = PostDispatchInfo { actual_weight: None, pays_fee: Pays::Yes };
+		assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			LEN,
+			&Ok(())
+		));
+		assert_eq!(Assets::balance(STABLE, ALICE), 1_000 * UNIT - fee);
+	});
+}
+
+#[test]
+fn asset_fee_goes_mostly_to_treasury() {
+	new_test_ext().execute_with(|| {
+		let treasury = Treasury::account_id();
+		assert_ok!(Assets::transfer(Origin::signed(ALICE), STABLE, treasury.clone().into(), UNIT));
+
+		let call = remark();
+		let info = call.get_dispatch_info();
+		let fee = TransactionPayment::compute_fee(LEN as u32, &info, 0);
+		let pre = ChargeAssetTxPayment::<Runtime>::from(0, Some(STABLE))
+			.pre_dispatch(&ALICE, &call, &info, LEN)
+			.unwrap();
+		let post_info = PostDispatchInfo { actual_weight: None, pays_fee: Pays::Yes };
+		assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			LEN,
+			&Ok(())
+		));
+
+		// The author's 20% is burned, as no author is set outside of block production.
+		assert_eq!(Assets::balance(STABLE, &treasury), UNIT + Perbill::from_percent(80) * fee);
+	});
+}
+
+#[test]
+fn fee_is_paid_in_native_token_without_asset() {
+	new_test_ext().execute_with(|| {
+		let call = remark();
+		let info = call.get_dispatch_info();
+		let fee = TransactionPayment::compute_fee(LEN as u32, &info, 0);
+
+		assert_ok!(
+			ChargeAssetTxPayment::<Runtime>::from(0, None).pre_dispatch(&ALICE, &call, &info, LEN)
+		);
+		assert_eq!(Balances::free_balance(&ALICE), 1_000 * UNIT - fee);
+		assert_eq!(Assets::balance(STABLE, ALICE), 1_000 * UNIT);
+	});
+}
+
+#[test]
+fn unknown_asset_cannot_pay_fees() {
+	new_test_ext().execute_with(|| {
+		let call = remark();
+		let info = call.get_dispatch_info();
+
+		assert!(ChargeAssetTxPayment::<Runtime>::from(0, Some(STABLE + 1))
+			.pre_dispatch(&ALICE, &call, &info, LEN)
+			.is_err());
+	});
+}
@@ -0,0 +1,33 @@
+//! The author found through Aura and the session validators is rewarded for every block.
+
+mod common;
+
+use codec::Encode;
+use common::{new_test_ext_with_validators, ALICE, BOB, UNIT};
+use frame_support::{assert_ok, traits::Hooks};
+use solar_node_runtime::{Authorship, Balances, BlockReward, Event, Origin, System};
+use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+use sp_runtime::{DigestItem, Perbill};
+
+#[test]
+fn block_author_is_rewarded() {
+	new_test_ext_with_validators().execute_with(|| {
+		assert_ok!(BlockReward::set_schedule(Origin::root(), vec![(0, Perbill::from_percent(5))]));
+		Balances::make_free_balance_be(&BOB, UNIT);
+		let reward = BlockReward::block_reward(1);
+		assert!(reward > 0);
+
+		// Slot 1 belongs to the second validator, Bob.
+		System::deposit_log(DigestItem::PreRuntime(AURA_ENGINE_ID, Slot::from(1u64).encode()));
+		Authorship::on_initialize(1);
+
+		assert_eq!(Authorship::author(), Some(BOB));
+		assert_eq!(Balances::free_balance(&BOB), UNIT + reward);
+		assert_eq!(BlockReward::total_rewards(BOB), reward);
+		System::assert_last_event(Event::BlockReward(pallet_solar_block_reward::Event::Rewarded {
+			author: BOB,
+			amount: reward,
+		}));
+		assert_eq!(BlockReward::total_rewards(ALICE), 0);
+	});
+}
@@ -0,0 +1,131 @@
+//! Contracts reach native balances, assets and NFTs through the functions of `SolarExtension`.
+
+mod common;
+
+use codec::{Decode, Encode};
+use common::{new_test_ext, ALICE, BOB, STABLE, UNIT};
+use frame_support::weights::Weight;
+use pallet_contracts_primitives::Code;
+use pallet_solar_nft::{CollectionId, TokenId};
+use solar_node_runtime::{AccountId, AssetId, Balance, Balances, Contracts, Nft, Runtime};
+use sp_core::Bytes;
+
+const GAS_LIMIT: Weight = 100_000_000_000;
+
+/// Status code of a successful call.
+const SUCCESS: u32 = 0;
+/// Status code of a failed transfer.
+const TRANSFER_FAILED: u32 = 1;
+
+fn instantiate_fixture() -> AccountId {
+	let wasm = wat::parse_str(include_str!("fixtures/chain_extension.wat")).unwrap();
+	Contracts::bare_instantiate(
+		ALICE,
+		UNIT,
+		GAS_LIMIT,
+		None,
+		Code::Upload(Bytes(wasm)),
+		vec![],
+		vec![],
+		false,
+	)
+	.result
+	.unwrap()
+	.account_id
+}
+
+/// Calls `func_id` with `input` and returns the status code and the output.
+fn call(contract: &AccountId, func_id: u32, input: impl Encode) -> (u32, Vec<u8>) {
+	let result = Contracts::bare_call(
+		ALICE,
+		contract.clone(),
+		0,
+		GAS_LIMIT,
+		None,
+		(func_id, input).encode(),
+		false,
+	)
+	.result
+	.unwrap();
+	let status = u32::decode(&mut &result.data[..4]).unwrap();
+	(status, result.data[4..].to_vec())
+}
+
+/// Calls `func_id` with `input`, which must succeed, and decodes its output.
+fn query<T: Decode>(contract: &AccountId, func_id: u32, input: impl Encode) -> T {
+	let (status, output) = call(contract, func_id, input);
+	assert_eq!(status, SUCCESS);
+	T::decode(&mut &output[..]).unwrap()
+}
+
+#[test]
+fn free_balance() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate_fixture();
+
+		assert_eq!(query::<Balance>(&contract, 1001, &ALICE), Balances::free_balance(&ALICE));
+		assert_eq!(query::<Balance>(&contract, 1001, &BOB), 0);
+	});
+}
+
+#[test]
+fn account_balance() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate_fixture();
+
+		let data: pallet_balances::AccountData<Balance> = query(&contract, 1002, &ALICE);
+		assert_eq!(data, frame_system::Account::<Runtime>::get(&ALICE).data);
+		assert_eq!(data.free, Balances::free_balance(&ALICE));
+	});
+}
+
+#[test]
+fn transfer_from_the_contract() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate_fixture();
+		let endowment = Balances::free_balance(&contract);
+
+		let (status, _) = call(&contract, 1003, (&BOB, UNIT / 2));
+		assert_eq!(status, SUCCESS);
+		assert_eq!(Balances::free_balance(&BOB), UNIT / 2);
+		assert_eq!(Balances::free_balance(&contract), endowment - UNIT / 2);
+
+		let (status, _) = call(&contract, 1003, (&BOB, endowment));
+		assert_eq!(status, TRANSFER_FAILED);
+		assert_eq!(Balances::free_balance(&BOB), UNIT / 2);
+	});
+}
+
+#[test]
+fn asset_balance() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate_fixture();
+
+		assert_eq!(query::<Balance>(&contract, 1004, (STABLE, &ALICE)), 1_000 * UNIT);
+		assert_eq!(query::<Balance>(&contract, 1004, (STABLE, &BOB)), 0);
+		let unknown: AssetId = STABLE + 1;
+		assert_eq!(query::<Balance>(&contract, 1004, (unknown, &ALICE)), 0);
+	});
+}
+
+#[test]
+fn nft_owner_and_balance() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate_fixture();
+		let collection_id: CollectionId =
+			Nft::do_create_collection(ALICE, b"name".to_vec(), b"symbol".to_vec()).unwrap();
+		let token_id: TokenId = 7;
+		Nft::do_mint(BOB, collection_id, token_id, None).unwrap();
+
+		assert_eq!(
+			query::<Option<AccountId>>(&contract, 1101, (collection_id, token_id)),
+			Some(BOB)
+		);
+		assert_eq!(
+			query::<Option<AccountId>>(&contract, 1101, (collection_id, token_id + 1)),
+			None
+		);
+		assert_eq!(query::<u64>(&contract, 1102, &BOB), 1);
+		assert_eq!(query::<u64>(&contract, 1102, &ALICE), 0);
+	});
+}
@@ -0,0 +1,87 @@
+//! Test externalities shared by the runtime integration tests.
+
+#![allow(dead_code)]
+
+use frame_support::traits::GenesisBuild;
+use pallet_grandpa::AuthorityId as GrandpaId;
+use solar_node_runtime::{opaque::SessionKeys, AccountId, AssetId, Balance, Runtime, System};
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_runtime::Storage;
+
+pub const UNIT: Balance = 1_000_000_000_000;
+pub const MILLIUNIT: Balance = UNIT / 1_000;
+pub const EXISTENTIAL_DEPOSIT: Balance = MILLIUNIT;
+
+pub const ALICE: AccountId = AccountId::new([1u8; 32]);
+pub const BOB: AccountId = AccountId::new([2u8; 32]);
+pub const CHARLIE: AccountId = AccountId::new([3u8; 32]);
+
+/// A sufficient asset priced one-to-one with the native token.
+pub const STABLE: AssetId = 1;
+
+/// Alice holds 1000 UNIT and 1000 `STABLE` and is the sudo key, `ContractsFilter` holds its
+/// initial whitelist, and the treasury account exists.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	test_ext_from(genesis_storage())
+}
+
+/// Genesis storage of [`new_test_ext`], for tests that need more genesis state.
+pub fn genesis_storage() -> Storage {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> { balances: vec![(ALICE, 1_000 * UNIT)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_assets::GenesisConfig::<Runtime> {
+		assets: vec![(STABLE, ALICE, true, EXISTENTIAL_DEPOSIT)],
+		metadata: vec![],
+		accounts: vec![(STABLE, ALICE, 1_000 * UNIT)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_sudo::GenesisConfig::<Runtime> { key: Some(ALICE) }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	GenesisBuild::<Runtime>::assimilate_storage(
+		&pallet_solar_call_filter::GenesisConfig {},
+		&mut t,
+	)
+	.unwrap();
+	GenesisBuild::<Runtime>::assimilate_storage(&pallet_treasury::GenesisConfig {}, &mut t)
+		.unwrap();
+	t
+}
+
+/// Like [`new_test_ext`], with Alice, Bob and Charlie validating the genesis session.
+pub fn new_test_ext_with_validators() -> sp_io::TestExternalities {
+	let validators = vec![ALICE, BOB, CHARLIE];
+	let mut t = genesis_storage();
+	pallet_solar_validator_set::GenesisConfig::<Runtime> { initial_validators: validators.clone() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_session::GenesisConfig::<Runtime> {
+		keys: validators
+			.iter()
+			.map(|v| {
+				let keys = SessionKeys { aura: aura_id(v), grandpa: grandpa_id(v) };
+				(v.clone(), v.clone(), keys)
+			})
+			.collect(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	test_ext_from(t)
+}
+
+pub fn test_ext_from(storage: Storage) -> sp_io::TestExternalities {
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn grandpa_id(who: &AccountId) -> GrandpaId {
+	sp_core::ed25519::Public::from_raw(who.clone().into()).into()
+}
+
+pub fn aura_id(who: &AccountId) -> AuraId {
+	sp_core::sr25519::Public::from_raw(who.clone().into()).into()
+}
@@ -0,0 +1,98 @@
+//! Contracts dispatching runtime calls through `seal_call_runtime` are limited to the calls
+//! whitelisted in `ContractsFilter`.
+
+mod common;
+
+use codec::{Decode, Encode};
+use common::{new_test_ext, ALICE, BOB, UNIT};
+use frame_support::{assert_ok, weights::Weight};
+use pallet_contracts_primitives::Code;
+use solar_node_runtime::{AccountId, Balance, Balances, Call, Contracts, ContractsFilter, Origin};
+use sp_core::Bytes;
+use sp_runtime::MultiAddress;
+
+const GAS_LIMIT: Weight = 100_000_000_000;
+/// `ReturnCode::CallRuntimeReturnedError`, returned when the call is filtered or fails.
+const CALL_RUNTIME_RETURNED_ERROR: u32 = 10;
+
+/// Instantiates the `call_runtime` fixture, endowed with 10 UNIT, and returns its address.
+fn instantiate_fixture() -> AccountId {
+	let wasm = wat::parse_str(include_str!("fixtures/call_runtime.wat")).unwrap();
+	Contracts::bare_instantiate(
+		ALICE,
+		10 * UNIT,
+		GAS_LIMIT,
+		None,
+		Code::Upload(Bytes(wasm)),
+		vec![],
+		vec![],
+		false,
+	)
+	.result
+	.unwrap()
+	.account_id
+}
+
+/// Makes the contract dispatch `call` and returns the `seal_call_runtime` return code.
+fn call_runtime(contract: &AccountId, call: Call) -> u32 {
+	let result =
+		Contracts::bare_call(ALICE, contract.clone(), 0, GAS_LIMIT, None, call.encode(), false)
+			.result
+			.unwrap();
+	u32::decode(&mut &result.data[..]).unwrap()
+}
+
+fn transfer_to_bob(value: Balance) -> Call {
+	Call::Balances(pallet_balances::Call::transfer { dest: MultiAddress::Id(BOB), value })
+}
+
+#[test]
+fn contract_can_dispatch_whitelisted_call() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate_fixture();
+
+		assert_eq!(call_runtime(&contract, transfer_to_bob(UNIT)), 0);
+		assert_eq!(Balances::free_balance(&BOB), UNIT);
+		assert_eq!(Balances::free_balance(&contract), 9 * UNIT);
+	});
+}
+
+#[test]
+fn contract_cannot_dispatch_other_calls() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate_fixture();
+
+		let remark = Call::System(frame_system::Call::remark { remark: b"hi".to_vec() });
+		assert_eq!(call_runtime(&contract, remark), CALL_RUNTIME_RETURNED_ERROR);
+
+		let transfer_all = Call::Balances(pallet_balances::Call::transfer_all {
+			dest: MultiAddress::Id(BOB),
+			keep_alive: false,
+		});
+		assert_eq!(call_runtime(&contract, transfer_all), CALL_RUNTIME_RETURNED_ERROR);
+		assert_eq!(Balances::free_balance(&BOB), 0);
+	});
+}
+
+#[test]
+fn governance_can_revoke_whitelisted_call() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate_fixture();
+
+		assert_ok!(ContractsFilter::disallow_call(
+			Origin::root(),
+			b"Balances".to_vec(),
+			b"transfer".to_vec()
+		));
+		assert_eq!(call_runtime(&contract, transfer_to_bob(UNIT)), CALL_RUNTIME_RETURNED_ERROR);
+		assert_eq!(Balances::free_balance(&BOB), 0);
+
+		assert_ok!(ContractsFilter::allow_call(
+			Origin::root(),
+			b"Balances".to_vec(),
+			b"transfer".to_vec()
+		));
+		assert_eq!(call_runtime(&contract, transfer_to_bob(UNIT)), 0);
+		assert_eq!(Balances::free_balance(&BOB), UNIT);
+	});
+}
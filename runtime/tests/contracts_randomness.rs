@@ -0,0 +1,69 @@
+//! `seal_random` returns the commit-reveal randomness of `CommitReveal`, which does not change
+//! within a block.
+
+mod common;
+
+use codec::Decode;
+use common::{new_test_ext, ALICE, UNIT};
+use frame_support::{
+	assert_ok,
+	traits::{Hooks, Randomness},
+	weights::Weight,
+};
+use pallet_contracts_primitives::Code;
+use solar_node_runtime::{AccountId, BlockNumber, CommitReveal, Contracts, Hash, Origin, System};
+use sp_core::{Bytes, H256};
+
+const GAS_LIMIT: Weight = 100_000_000_000;
+const SUBJECT: &[u8] = b"blind box";
+
+fn instantiate_fixture() -> AccountId {
+	let wasm = wat::parse_str(include_str!("fixtures/random.wat")).unwrap();
+	Contracts::bare_instantiate(
+		ALICE,
+		UNIT,
+		GAS_LIMIT,
+		None,
+		Code::Upload(Bytes(wasm)),
+		vec![],
+		vec![],
+		false,
+	)
+	.result
+	.unwrap()
+	.account_id
+}
+
+fn seal_random(contract: &AccountId) -> (Hash, BlockNumber) {
+	let result =
+		Contracts::bare_call(ALICE, contract.clone(), 0, GAS_LIMIT, None, SUBJECT.to_vec(), false)
+			.result
+			.unwrap();
+	Decode::decode(&mut &result.data[..]).unwrap()
+}
+
+fn next_block() {
+	let next = System::block_number() + 1;
+	System::set_block_number(next);
+	CommitReveal::on_initialize(next);
+}
+
+#[test]
+fn contracts_get_commit_reveal_randomness() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate_fixture();
+		let secret = H256::repeat_byte(7);
+		let hash = CommitReveal::commitment_hash(&ALICE, &secret);
+		assert_ok!(CommitReveal::commit(Origin::signed(ALICE), hash));
+		next_block();
+
+		let before = seal_random(&contract);
+		assert_ok!(CommitReveal::reveal(Origin::signed(ALICE), secret));
+		assert_eq!(seal_random(&contract), before);
+
+		next_block();
+		let after = seal_random(&contract);
+		assert_ne!(after, before);
+		assert_eq!(after, CommitReveal::random(SUBJECT));
+	});
+}
@@ -0,0 +1,112 @@
+//! Proxies limited to NFT or contract calls, batching through `Utility`, and multisig accounts.
+
+mod common;
+
+use codec::Encode;
+use common::{new_test_ext, ALICE, BOB, CHARLIE, UNIT};
+use frame_support::{assert_ok, weights::GetDispatchInfo};
+use pallet_multisig::OpaqueCall;
+use solar_node_runtime::{
+	Balances, Call, Event, Multisig, Nft, Origin, Proxy, ProxyType, Runtime, System,
+};
+use sp_runtime::{DispatchError, MultiAddress};
+
+fn mint(token_id: u64) -> Call {
+	Call::Nft(pallet_solar_nft::Call::mint {
+		to: MultiAddress::Id(CHARLIE),
+		collection_id: 0,
+		token_id,
+		metadata: None,
+	})
+}
+
+fn transfer_to_charlie(value: u128) -> Call {
+	Call::Balances(pallet_balances::Call::transfer { dest: MultiAddress::Id(CHARLIE), value })
+}
+
+fn assert_proxy_executed(result: Result<(), DispatchError>) {
+	System::assert_last_event(Event::Proxy(pallet_proxy::Event::ProxyExecuted { result }));
+}
+
+#[test]
+fn nft_proxy_can_batch_mint() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create_collection(Origin::signed(ALICE), b"drop".to_vec(), b"D".to_vec()));
+		assert_ok!(Proxy::add_proxy(Origin::signed(ALICE), BOB, ProxyType::Nft, 0));
+
+		let batch =
+			Call::Utility(pallet_utility::Call::batch_all { calls: vec![mint(1), mint(2)] });
+		assert_ok!(Proxy::proxy(Origin::signed(BOB), ALICE, None, Box::new(batch)));
+		assert_proxy_executed(Ok(()));
+
+		assert_eq!(Nft::owner_of(0, 1), Some(CHARLIE));
+		assert_eq!(Nft::owner_of(0, 2), Some(CHARLIE));
+	});
+}
+
+#[test]
+fn nft_proxy_cannot_transfer_funds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy(Origin::signed(ALICE), BOB, ProxyType::Nft, 0));
+		let filtered: DispatchError = frame_system::Error::<Runtime>::CallFiltered.into();
+
+		let transfer = transfer_to_charlie(UNIT);
+		assert_ok!(Proxy::proxy(Origin::signed(BOB), ALICE, None, Box::new(transfer.clone())));
+		assert_proxy_executed(Err(filtered));
+
+		// Batched calls are filtered one by one.
+		let batch = Call::Utility(pallet_utility::Call::batch_all { calls: vec![transfer] });
+		assert_ok!(Proxy::proxy(Origin::signed(BOB), ALICE, None, Box::new(batch)));
+		assert_eq!(Balances::free_balance(&CHARLIE), 0);
+	});
+}
+
+#[test]
+fn contracts_proxy_cannot_call_nft() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create_collection(Origin::signed(ALICE), b"drop".to_vec(), b"D".to_vec()));
+		assert_ok!(Proxy::add_proxy(Origin::signed(ALICE), BOB, ProxyType::Contracts, 0));
+
+		assert_ok!(Proxy::proxy(Origin::signed(BOB), ALICE, None, Box::new(mint(1))));
+		assert_proxy_executed(Err(frame_system::Error::<Runtime>::CallFiltered.into()));
+		assert_eq!(Nft::owner_of(0, 1), None);
+	});
+}
+
+#[test]
+fn multisig_transfers_after_threshold() {
+	new_test_ext().execute_with(|| {
+		let multisig = Multisig::multi_account_id(&[ALICE, BOB, CHARLIE], 2);
+		assert_ok!(Balances::transfer(
+			Origin::signed(ALICE),
+			MultiAddress::Id(multisig),
+			10 * UNIT
+		));
+
+		let call = transfer_to_charlie(UNIT);
+		let weight = call.get_dispatch_info().weight;
+		let data = call.encode();
+
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(ALICE),
+			2,
+			vec![BOB, CHARLIE],
+			None,
+			OpaqueCall::<Runtime>::from_encoded(data.clone()),
+			false,
+			0
+		));
+		assert_eq!(Balances::free_balance(&CHARLIE), 0);
+
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(BOB),
+			2,
+			vec![ALICE, CHARLIE],
+			Some(Multisig::timepoint()),
+			OpaqueCall::<Runtime>::from_encoded(data),
+			false,
+			weight
+		));
+		assert_eq!(Balances::free_balance(&CHARLIE), UNIT);
+	});
+}
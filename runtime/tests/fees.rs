@@ -0,0 +1,81 @@
+//! Fees are priced against `UNIT`, follow block fullness, and are split between the treasury and
+//! the block author.
+
+mod common;
+
+use codec::Encode;
+use common::{new_test_ext, new_test_ext_with_validators, ALICE, BOB, MILLIUNIT, UNIT};
+use frame_support::{
+	assert_ok,
+	traits::Hooks,
+	weights::{
+		constants::ExtrinsicBaseWeight, DispatchClass, GetDispatchInfo, Pays, PostDispatchInfo,
+	},
+};
+use pallet_asset_tx_payment::ChargeAssetTxPayment;
+use pallet_transaction_payment::Multiplier;
+use solar_node_runtime::{
+	Balances, Call, Runtime, RuntimeBlockWeights, System, TransactionPayment, Treasury,
+};
+use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+use sp_runtime::{
+	traits::{One, SignedExtension},
+	DigestItem,
+};
+
+const LEN: usize = 100;
+
+#[test]
+fn base_extrinsic_costs_a_milliunit() {
+	new_test_ext().execute_with(|| {
+		let fee = TransactionPayment::weight_to_fee(ExtrinsicBaseWeight::get());
+		assert!(fee <= MILLIUNIT && fee > MILLIUNIT * 99 / 100);
+		assert!(TransactionPayment::weight_to_fee(10 * ExtrinsicBaseWeight::get()) > 9 * fee);
+	});
+}
+
+#[test]
+fn multiplier_follows_block_fullness() {
+	new_test_ext().execute_with(|| {
+		let max_normal = RuntimeBlockWeights::get().get(DispatchClass::Normal).max_total.unwrap();
+
+		System::set_block_consumed_resources(max_normal, 0);
+		TransactionPayment::on_finalize(1);
+		let congested = TransactionPayment::next_fee_multiplier();
+		assert!(congested > Multiplier::one());
+
+		System::set_block_consumed_resources(0, 0);
+		TransactionPayment::on_finalize(2);
+		assert!(TransactionPayment::next_fee_multiplier() < congested);
+	});
+}
+
+#[test]
+fn fees_are_split_between_treasury_and_author() {
+	new_test_ext_with_validators().execute_with(|| {
+		// Slot 1 belongs to the second validator, Bob.
+		System::deposit_log(DigestItem::PreRuntime(AURA_ENGINE_ID, Slot::from(1u64).encode()));
+		Balances::make_free_balance_be(&BOB, UNIT);
+		let treasury = Balances::free_balance(&Treasury::account_id());
+
+		let call = Call::System(frame_system::Call::remark { remark: vec![0u8; 32] });
+		let info = call.get_dispatch_info();
+		let fee = TransactionPayment::compute_fee(LEN as u32, &info, 0);
+		let pre = ChargeAssetTxPayment::<Runtime>::from(0, None)
+			.pre_dispatch(&ALICE, &call, &info, LEN)
+			.unwrap();
+		let post_info = PostDispatchInfo { actual_weight: None, pays_fee: Pays::Yes };
+		assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			LEN,
+			&Ok(())
+		));
+
+		let to_treasury = fee * 80 / 100;
+		assert_eq!(Balances::free_balance(&ALICE), 1_000 * UNIT - fee);
+		assert_eq!(Balances::free_balance(&Treasury::account_id()), treasury + to_treasury);
+		assert_eq!(Balances::free_balance(&BOB), UNIT + fee - to_treasury);
+	});
+}
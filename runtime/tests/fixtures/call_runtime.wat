@@ -0,0 +1,33 @@
+;; Passes its input to `seal_call_runtime` and returns the return code to its caller.
+(module
+	(import "__unstable__" "seal_call_runtime" (func $seal_call_runtime (param i32 i32) (result i32)))
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) size of the input buffer, 0x1000 in little endian
+	(data (i32.const 0) "\00\10")
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		;; Receive the encoded call at offset 4
+		(call $seal_input
+			(i32.const 4)	;; Pointer to the input buffer
+			(i32.const 0)	;; Pointer to the input buffer size
+		)
+		;; Dispatch it and store the return code at offset 0
+		(i32.store
+			(i32.const 0)
+			(call $seal_call_runtime
+				(i32.const 4)				;; Pointer to the encoded call
+				(i32.load (i32.const 0))	;; Length of the encoded call
+			)
+		)
+		(call $seal_return
+			(i32.const 0)	;; flags
+			(i32.const 0)	;; Pointer to the return code
+			(i32.const 4)	;; Length of the return code
+		)
+	)
+)
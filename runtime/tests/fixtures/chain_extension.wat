@@ -0,0 +1,48 @@
+;; Calls the chain extension function whose `func_id` is the first 4 bytes of its input, passing
+;; the rest of the input, and returns the status code followed by the output of the function.
+(module
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) size of the input buffer, 256 bytes
+	(data (i32.const 0) "\00\01")
+
+	;; [4, 8) size of the output buffer, 256 bytes
+	(data (i32.const 4) "\00\01")
+
+	;; [8, 264) input buffer
+
+	;; [264, 268) status code
+
+	;; [268, 524) output buffer
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		;; Receive the func_id and the input of the function at offset 8
+		(call $seal_input
+			(i32.const 8)	;; Pointer to the input buffer
+			(i32.const 0)	;; Pointer to the input buffer size
+		)
+		;; Write the status code at offset 264 and the output after it
+		(i32.store
+			(i32.const 264)
+			(call $seal_call_chain_extension
+				(i32.load (i32.const 8))						;; func_id
+				(i32.const 12)									;; Pointer to the input
+				(i32.sub (i32.load (i32.const 0)) (i32.const 4))	;; Length of the input
+				(i32.const 268)									;; Pointer to the output buffer
+				(i32.const 4)									;; Pointer to the output buffer size
+			)
+		)
+		(call $seal_return
+			(i32.const 0)									;; flags
+			(i32.const 264)									;; Pointer to the status code
+			(i32.add (i32.load (i32.const 4)) (i32.const 4))	;; Length of status and output
+		)
+	)
+)
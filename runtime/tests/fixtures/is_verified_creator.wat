@@ -0,0 +1,41 @@
+;; Returns the output of the `is_verified_creator` chain extension, a `bool`, for the account it
+;; receives as input.
+(module
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) size of the input buffer, 32 bytes
+	(data (i32.const 0) "\20")
+
+	;; [4, 8) size of the output buffer, 1 byte
+	(data (i32.const 4) "\01")
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		;; Receive the account at offset 8
+		(call $seal_input
+			(i32.const 8)	;; Pointer to the input buffer
+			(i32.const 0)	;; Pointer to the input buffer size
+		)
+		;; Write the flag for it at offset 40
+		(drop
+			(call $seal_call_chain_extension
+				(i32.const 1201)			;; func_id
+				(i32.const 8)				;; Pointer to the account
+				(i32.load (i32.const 0))	;; Length of the account
+				(i32.const 40)				;; Pointer to the output buffer
+				(i32.const 4)				;; Pointer to the output buffer size
+			)
+		)
+		(call $seal_return
+			(i32.const 0)				;; flags
+			(i32.const 40)				;; Pointer to the output
+			(i32.load (i32.const 4))	;; Length of the output
+		)
+	)
+)
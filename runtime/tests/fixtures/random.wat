@@ -0,0 +1,35 @@
+;; Returns the `seal_random` output, `(Hash, BlockNumber)`, for the subject it receives as input.
+(module
+	(import "seal1" "seal_random" (func $seal_random (param i32 i32 i32 i32)))
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) size of the input buffer, 32 bytes
+	(data (i32.const 0) "\20")
+
+	;; [4, 8) size of the output buffer, 64 bytes
+	(data (i32.const 4) "\40")
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		;; Receive the subject at offset 8
+		(call $seal_input
+			(i32.const 8)	;; Pointer to the input buffer
+			(i32.const 0)	;; Pointer to the input buffer size
+		)
+		;; Write the randomness for it at offset 40
+		(call $seal_random
+			(i32.const 8)				;; Pointer to the subject
+			(i32.load (i32.const 0))	;; Length of the subject
+			(i32.const 40)				;; Pointer to the output buffer
+			(i32.const 4)				;; Pointer to the output buffer size
+		)
+		(call $seal_return
+			(i32.const 0)				;; flags
+			(i32.const 40)				;; Pointer to the output
+			(i32.load (i32.const 4))	;; Length of the output
+		)
+	)
+)
@@ -0,0 +1,70 @@
+//! The council governs node authorization, and takes over from the sudo key on upgrade.
+
+mod common;
+
+use common::{new_test_ext, ALICE, BOB};
+use frame_support::{assert_noop, assert_ok, traits::OnRuntimeUpgrade};
+use solar_node_runtime::{
+	migrations::RetireSudo, AccountId, Call, CouncilMembership, NodeAuthorization, Origin, Runtime,
+	Sudo,
+};
+use sp_core::OpaquePeerId;
+use sp_runtime::DispatchError;
+
+type CouncilOrigin = pallet_collective::RawOrigin<AccountId, pallet_collective::Instance1>;
+
+fn peer_id() -> OpaquePeerId {
+	OpaquePeerId(vec![1, 2, 3])
+}
+
+#[test]
+fn council_majority_can_authorize_nodes() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			NodeAuthorization::add_well_known_node(Origin::signed(ALICE), peer_id(), ALICE),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			NodeAuthorization::add_well_known_node(
+				CouncilOrigin::Members(1, 2).into(),
+				peer_id(),
+				ALICE
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(NodeAuthorization::add_well_known_node(
+			CouncilOrigin::Members(2, 3).into(),
+			peer_id(),
+			ALICE
+		));
+		assert_ok!(NodeAuthorization::remove_well_known_node(
+			CouncilOrigin::Members(2, 3).into(),
+			peer_id()
+		));
+	});
+}
+
+#[test]
+fn sudo_key_is_kept_without_council() {
+	new_test_ext().execute_with(|| {
+		RetireSudo::on_runtime_upgrade();
+		assert_eq!(Sudo::key(), Some(ALICE));
+	});
+}
+
+#[test]
+fn sudo_key_is_retired_once_council_exists() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CouncilMembership::reset_members(Origin::root(), vec![ALICE, BOB]));
+
+		RetireSudo::on_runtime_upgrade();
+		assert_eq!(Sudo::key(), None);
+
+		let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+		assert_noop!(
+			Sudo::sudo(Origin::signed(ALICE), Box::new(remark)),
+			pallet_sudo::Error::<Runtime>::RequireSudo
+		);
+	});
+}
@@ -0,0 +1,36 @@
+//! GRANDPA equivocation reports are checked against historical sessions and disable the offender.
+
+mod common;
+
+use common::{grandpa_id, new_test_ext_with_validators, ALICE, BOB, CHARLIE};
+use frame_support::traits::KeyOwnerProofSystem;
+use pallet_grandpa::{fg_primitives::KEY_TYPE, GrandpaEquivocationOffence, GrandpaTimeSlot};
+use solar_node_runtime::{AccountId, Historical, Offences, Session, ValidatorSet};
+use sp_staking::offence::ReportOffence;
+
+#[test]
+fn key_ownership_is_proven_against_historical_session() {
+	new_test_ext_with_validators().execute_with(|| {
+		let key = (KEY_TYPE, grandpa_id(&BOB));
+		let proof = Historical::prove(key.clone()).unwrap();
+		assert_eq!(Historical::check_proof(key, proof), Some((BOB, BOB)));
+
+		assert!(Historical::prove((KEY_TYPE, grandpa_id(&AccountId::new([9u8; 32])))).is_none());
+	});
+}
+
+#[test]
+fn reported_equivocation_disables_offender() {
+	new_test_ext_with_validators().execute_with(|| {
+		let offence = GrandpaEquivocationOffence {
+			time_slot: GrandpaTimeSlot { set_id: 0, round: 1 },
+			session_index: Session::current_index(),
+			validator_set_count: 3,
+			offender: (BOB, BOB),
+		};
+		assert!(Offences::report_offence(vec![], offence).is_ok());
+
+		assert_eq!(Session::disabled_validators(), vec![1]);
+		assert_eq!(ValidatorSet::validators().to_vec(), vec![ALICE, CHARLIE]);
+	});
+}
@@ -0,0 +1,52 @@
+//! The runtime side of the `solar_nft` RPC.
+
+mod common;
+
+use common::{new_test_ext, ALICE, BOB};
+use frame_support::assert_ok;
+use pallet_solar_nft_rpc_runtime_api::{runtime_decl_for_NftApi::NftApi, MAX_TOKENS_PER_PAGE};
+use solar_node_runtime::{AccountId, Block, Nft, Origin, Runtime};
+
+#[test]
+fn tokens_of_is_paged() {
+	new_test_ext().execute_with(|| {
+		let collection_id =
+			Nft::do_create_collection(ALICE, b"drop".to_vec(), b"D".to_vec()).unwrap();
+		for token_id in 0..=u64::from(MAX_TOKENS_PER_PAGE) {
+			assert_ok!(Nft::do_mint(BOB, collection_id, token_id, None));
+		}
+
+		// The limit is capped, the rest is on the next page.
+		let first = <Runtime as NftApi<Block, AccountId>>::tokens_of(BOB, 0, u32::MAX);
+		assert_eq!(first.len(), MAX_TOKENS_PER_PAGE as usize);
+		let rest = <Runtime as NftApi<Block, AccountId>>::tokens_of(BOB, MAX_TOKENS_PER_PAGE, 10);
+		assert_eq!(rest.len(), 1);
+		assert!(!first.contains(&rest[0]));
+
+		assert_eq!(
+			<Runtime as NftApi<Block, AccountId>>::tokens_of(BOB, 1, 2),
+			first[1..3].to_vec()
+		);
+		assert!(<Runtime as NftApi<Block, AccountId>>::tokens_of(ALICE, 0, 10).is_empty());
+	});
+}
+
+#[test]
+fn token_and_collection_info() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create_collection(Origin::signed(ALICE), b"drop".to_vec(), b"D".to_vec()));
+		assert_ok!(Nft::mint(Origin::signed(ALICE), BOB.into(), 0, 7, Some(b"ipfs://7".to_vec())));
+
+		assert_eq!(<Runtime as NftApi<Block, AccountId>>::owner_of(0, 7), Some(BOB));
+		let token = <Runtime as NftApi<Block, AccountId>>::token_info(0, 7).unwrap();
+		assert_eq!(token.owner, BOB);
+		assert_eq!(token.metadata, Some(b"ipfs://7".to_vec()));
+		let collection = <Runtime as NftApi<Block, AccountId>>::collection_info(0).unwrap();
+		assert_eq!(collection.owner, ALICE);
+		assert_eq!(collection.name, b"drop".to_vec());
+		assert_eq!(collection.total_supply, 1);
+
+		assert_eq!(<Runtime as NftApi<Block, AccountId>>::token_info(0, 8), None);
+		assert_eq!(<Runtime as NftApi<Block, AccountId>>::collection_info(1), None);
+	});
+}
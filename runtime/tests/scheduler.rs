@@ -0,0 +1,76 @@
+//! Calls can be scheduled by the hash of a noted preimage, by root or a council majority.
+
+mod common;
+
+use codec::Encode;
+use common::{new_test_ext, ALICE, BOB, UNIT};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{schedule::MaybeHashed, Hooks, PreimageProvider},
+};
+use solar_node_runtime::{
+	AccountId, Balances, BlockNumber, Call, Origin, Preimage, Scheduler, System,
+};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError, MultiAddress,
+};
+
+type CouncilOrigin = pallet_collective::RawOrigin<AccountId, pallet_collective::Instance1>;
+
+fn force_transfer_to_bob() -> Call {
+	Call::Balances(pallet_balances::Call::force_transfer {
+		source: MultiAddress::Id(ALICE),
+		dest: MultiAddress::Id(BOB),
+		value: UNIT,
+	})
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		Scheduler::on_initialize(next);
+	}
+}
+
+#[test]
+fn call_scheduled_by_hash_runs_from_preimage() {
+	new_test_ext().execute_with(|| {
+		let call = force_transfer_to_bob();
+		let hash = BlakeTwo256::hash_of(&call);
+		assert_ok!(Preimage::note_preimage(Origin::signed(ALICE), call.encode()));
+
+		assert_ok!(Scheduler::schedule_named(
+			Origin::root(),
+			b"pay bob".to_vec(),
+			3,
+			None,
+			0,
+			Box::new(MaybeHashed::Hash(hash))
+		));
+		assert!(Preimage::preimage_requested(&hash));
+
+		run_to_block(2);
+		assert_eq!(Balances::free_balance(&BOB), 0);
+		run_to_block(3);
+		assert_eq!(Balances::free_balance(&BOB), UNIT);
+	});
+}
+
+#[test]
+fn council_majority_can_schedule() {
+	new_test_ext().execute_with(|| {
+		let schedule = |origin: Origin| {
+			Scheduler::schedule(origin, 3, None, 0, Box::new(MaybeHashed::Value(remark())))
+		};
+
+		assert_noop!(schedule(Origin::signed(ALICE)), DispatchError::BadOrigin);
+		assert_noop!(schedule(CouncilOrigin::Members(1, 2).into()), DispatchError::BadOrigin);
+		assert_ok!(schedule(CouncilOrigin::Members(2, 3).into()));
+	});
+}
@@ -0,0 +1,72 @@
+//! The council approves treasury spends, and trade commissions fund the treasury.
+
+mod common;
+
+use common::{new_test_ext, ALICE, BOB, UNIT};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade},
+};
+use solar_node_runtime::{
+	AccountId, Balances, CommissionToTreasury, Market, Origin, Runtime, SpendPeriod, System,
+	Treasury,
+};
+use sp_runtime::{DispatchError, MultiAddress};
+
+type CouncilOrigin = pallet_collective::RawOrigin<AccountId, pallet_collective::Instance1>;
+
+#[test]
+fn council_approved_spend_is_paid_out() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::transfer(
+			Origin::signed(ALICE),
+			MultiAddress::Id(Treasury::account_id()),
+			100 * UNIT
+		));
+
+		assert_ok!(Treasury::propose_spend(
+			Origin::signed(ALICE),
+			10 * UNIT,
+			MultiAddress::Id(BOB)
+		));
+		assert_noop!(
+			Treasury::approve_proposal(CouncilOrigin::Members(1, 2).into(), 0),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Treasury::approve_proposal(CouncilOrigin::Members(3, 5).into(), 0));
+
+		let spend_period = SpendPeriod::get();
+		System::set_block_number(spend_period);
+		Treasury::on_initialize(spend_period);
+		assert_eq!(Balances::free_balance(&BOB), 10 * UNIT);
+	});
+}
+
+#[test]
+fn council_majority_can_reject_spend() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Treasury::propose_spend(
+			Origin::signed(ALICE),
+			10 * UNIT,
+			MultiAddress::Id(BOB)
+		));
+		assert_ok!(Treasury::reject_proposal(CouncilOrigin::Members(2, 3).into(), 0));
+		// The bond of 5%, at least one UNIT, goes to the treasury.
+		assert_eq!(Balances::free_balance(&ALICE), 1_000 * UNIT - UNIT);
+	});
+}
+
+#[test]
+fn commissions_go_to_treasury_after_upgrade() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Market::on_chain_storage_version(), 0);
+		CommissionToTreasury::on_runtime_upgrade();
+		assert_eq!(Market::organiser(), Some(Treasury::account_id()));
+		assert_eq!(Market::on_chain_storage_version(), 1);
+
+		// An organiser chosen through governance later survives further upgrades.
+		pallet_solar_market::Organiser::<Runtime>::put(BOB);
+		CommissionToTreasury::on_runtime_upgrade();
+		assert_eq!(Market::organiser(), Some(BOB));
+	});
+}
@@ -0,0 +1,66 @@
+//! A chain started with static Aura and GRANDPA authorities hands them to the session and the
+//! validator set on upgrade.
+
+mod common;
+
+use common::{aura_id, genesis_storage, grandpa_id, test_ext_from, ALICE, BOB};
+use frame_support::traits::{GenesisBuild, OnRuntimeUpgrade};
+use solar_node_runtime::{
+	migrations::SeedValidatorSet, opaque::SessionKeys, Aura, Grandpa, Runtime, Session,
+	ValidatorSet,
+};
+use sp_runtime::KeyTypeId;
+
+fn new_test_ext_with_static_authorities() -> sp_io::TestExternalities {
+	let mut t = genesis_storage();
+	pallet_aura::GenesisConfig::<Runtime> { authorities: vec![aura_id(&ALICE), aura_id(&BOB)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	GenesisBuild::<Runtime>::assimilate_storage(
+		&pallet_grandpa::GenesisConfig {
+			authorities: vec![(grandpa_id(&ALICE), 1), (grandpa_id(&BOB), 1)],
+		},
+		&mut t,
+	)
+	.unwrap();
+	test_ext_from(t)
+}
+
+#[test]
+fn static_authorities_become_validators() {
+	new_test_ext_with_static_authorities().execute_with(|| {
+		SeedValidatorSet::on_runtime_upgrade();
+
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![ALICE, BOB]);
+		assert_eq!(Session::validators(), vec![ALICE, BOB]);
+		let keys = SessionKeys { aura: aura_id(&BOB), grandpa: grandpa_id(&BOB) };
+		assert_eq!(pallet_session::NextKeys::<Runtime>::get(&BOB), Some(keys));
+		assert_eq!(
+			pallet_session::KeyOwner::<Runtime>::get((
+				KeyTypeId(*b"gran"),
+				<[u8; 32]>::from(BOB).to_vec()
+			)),
+			Some(BOB)
+		);
+
+		// The authorities survive the next session changes.
+		Session::rotate_session();
+		Session::rotate_session();
+		assert_eq!(Aura::authorities().into_inner(), vec![aura_id(&ALICE), aura_id(&BOB)]);
+		assert_eq!(Grandpa::grandpa_authorities().len(), 2);
+		assert_eq!(Session::validators(), vec![ALICE, BOB]);
+	});
+}
+
+#[test]
+fn seeded_chains_are_left_alone() {
+	new_test_ext_with_static_authorities().execute_with(|| {
+		SeedValidatorSet::on_runtime_upgrade();
+		pallet_solar_validator_set::Validators::<Runtime>::put(
+			frame_support::BoundedVec::try_from(vec![ALICE]).unwrap(),
+		);
+
+		SeedValidatorSet::on_runtime_upgrade();
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![ALICE]);
+	});
+}
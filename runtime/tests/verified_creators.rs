@@ -0,0 +1,86 @@
+//! Accounts whose identity a registrar judged `Reasonable` or `KnownGood` are verified creators,
+//! which contracts read through the `is_verified_creator` chain extension.
+
+mod common;
+
+use codec::{Decode, Encode};
+use common::{new_test_ext, ALICE, BOB, UNIT};
+use frame_support::{assert_ok, weights::Weight};
+use pallet_contracts_primitives::Code;
+use pallet_identity::{Data, IdentityInfo, Judgement};
+use solar_node_runtime::{AccountId, Balances, Contracts, Identity, Origin};
+use sp_core::Bytes;
+
+const GAS_LIMIT: Weight = 100_000_000_000;
+
+fn instantiate_fixture() -> AccountId {
+	let wasm = wat::parse_str(include_str!("fixtures/is_verified_creator.wat")).unwrap();
+	Contracts::bare_instantiate(
+		ALICE,
+		UNIT,
+		GAS_LIMIT,
+		None,
+		Code::Upload(Bytes(wasm)),
+		vec![],
+		vec![],
+		false,
+	)
+	.result
+	.unwrap()
+	.account_id
+}
+
+fn is_verified_creator(contract: &AccountId, who: &AccountId) -> bool {
+	let result =
+		Contracts::bare_call(ALICE, contract.clone(), 0, GAS_LIMIT, None, who.encode(), false)
+			.result
+			.unwrap();
+	Decode::decode(&mut &result.data[..]).unwrap()
+}
+
+fn set_identity(who: &AccountId, display: &[u8]) {
+	let info = IdentityInfo {
+		additional: Default::default(),
+		display: Data::Raw(display.to_vec().try_into().unwrap()),
+		legal: Data::None,
+		web: Data::None,
+		riot: Data::None,
+		email: Data::None,
+		pgp_fingerprint: None,
+		image: Data::None,
+		twitter: Data::None,
+	};
+	assert_ok!(Identity::set_identity(Origin::signed(who.clone()), Box::new(info)));
+}
+
+fn judge(who: &AccountId, judgement: Judgement<u128>) {
+	assert_ok!(Identity::provide_judgement(Origin::signed(BOB), 0, who.clone().into(), judgement));
+}
+
+#[test]
+fn registrar_judgements_verify_creators() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::transfer(Origin::signed(ALICE), BOB.into(), 10 * UNIT));
+		assert_ok!(Identity::add_registrar(Origin::root(), BOB));
+		let contract = instantiate_fixture();
+
+		assert!(!is_verified_creator(&contract, &ALICE));
+
+		set_identity(&ALICE, b"alice");
+		assert!(!is_verified_creator(&contract, &ALICE));
+
+		judge(&ALICE, Judgement::LowQuality);
+		assert!(!is_verified_creator(&contract, &ALICE));
+
+		judge(&ALICE, Judgement::Reasonable);
+		assert!(is_verified_creator(&contract, &ALICE));
+		assert!(!is_verified_creator(&contract, &BOB));
+
+		// Changing the identity drops its judgements.
+		set_identity(&ALICE, b"alice2");
+		assert!(!is_verified_creator(&contract, &ALICE));
+
+		judge(&ALICE, Judgement::KnownGood);
+		assert!(is_verified_creator(&contract, &ALICE));
+	});
+}
@@ -0,0 +1,75 @@
+//! Genesis allocations vest linearly per block, and vested transfers lock what they send until it
+//! vests.
+
+mod common;
+
+use common::{genesis_storage, new_test_ext, test_ext_from, ALICE, BOB, UNIT};
+use frame_support::{assert_noop, assert_ok};
+use pallet_vesting::VestingInfo;
+use solar_node_runtime::{Balances, Origin, Runtime, System, Vesting};
+
+/// Like [`new_test_ext`], with 100 of Alice's 1000 UNIT vesting 1 UNIT a block from genesis.
+fn new_test_ext_with_vesting() -> sp_io::TestExternalities {
+	let mut t = genesis_storage();
+	pallet_vesting::GenesisConfig::<Runtime> { vesting: vec![(ALICE, 0, 100, 900 * UNIT)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	test_ext_from(t)
+}
+
+#[test]
+fn genesis_allocations_unlock_per_block() {
+	new_test_ext_with_vesting().execute_with(|| {
+		assert_eq!(Vesting::vesting_balance(&ALICE), Some(99 * UNIT));
+		assert_noop!(
+			Balances::transfer_keep_alive(Origin::signed(ALICE), BOB.into(), 950 * UNIT),
+			pallet_balances::Error::<Runtime>::LiquidityRestrictions
+		);
+
+		System::set_block_number(50);
+		assert_ok!(Vesting::vest(Origin::signed(ALICE)));
+		assert_eq!(Vesting::vesting_balance(&ALICE), Some(50 * UNIT));
+		assert_ok!(Balances::transfer_keep_alive(Origin::signed(ALICE), BOB.into(), 940 * UNIT));
+		assert_noop!(
+			Balances::transfer_keep_alive(Origin::signed(ALICE), BOB.into(), 15 * UNIT),
+			pallet_balances::Error::<Runtime>::LiquidityRestrictions
+		);
+
+		System::set_block_number(100);
+		assert_ok!(Vesting::vest(Origin::signed(ALICE)));
+		assert_eq!(Vesting::vesting_balance(&ALICE), None);
+		assert!(Balances::locks(&ALICE).is_empty());
+		assert_ok!(Balances::transfer_keep_alive(Origin::signed(ALICE), BOB.into(), 50 * UNIT));
+	});
+}
+
+#[test]
+fn vested_transfers_lock_until_vested() {
+	new_test_ext().execute_with(|| {
+		let schedule = VestingInfo::new(10 * UNIT, UNIT, 1);
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB.into(), schedule));
+		assert_eq!(Balances::free_balance(&BOB), 10 * UNIT);
+		assert_eq!(Vesting::vesting_balance(&BOB), Some(10 * UNIT));
+		assert_noop!(
+			Balances::transfer(Origin::signed(BOB), ALICE.into(), UNIT),
+			pallet_balances::Error::<Runtime>::LiquidityRestrictions
+		);
+
+		System::set_block_number(6);
+		assert_ok!(Vesting::vest(Origin::signed(BOB)));
+		assert_eq!(Vesting::vesting_balance(&BOB), Some(5 * UNIT));
+		assert_ok!(Balances::transfer(Origin::signed(BOB), ALICE.into(), 4 * UNIT));
+		assert_eq!(Balances::free_balance(&BOB), 6 * UNIT);
+	});
+}
+
+#[test]
+fn vested_transfers_below_minimum_fail() {
+	new_test_ext().execute_with(|| {
+		let schedule = VestingInfo::new(UNIT / 2, UNIT / 20, 1);
+		assert_noop!(
+			Vesting::vested_transfer(Origin::signed(ALICE), BOB.into(), schedule),
+			pallet_vesting::Error::<Runtime>::AmountLow
+		);
+	});
+}
@@ -0,0 +1,80 @@
+#!/bin/bash
+#
+# Runs the runtime benchmarks and writes the weights to `runtime/src/weights/<pallet>.rs`.
+#
+# Usage: scripts/benchmark.sh [pallet ...]
+#
+# Without arguments every benchmarked pallet is run. Run it on the reference hardware only,
+# `solar-node benchmark machine` tells whether a machine qualifies.
+#
+# The runtime does not use the generated modules until they are declared in
+# `runtime/src/weights/mod.rs`, that module in `runtime/src/lib.rs`, and each is set as the
+# pallet's `WeightInfo`. Until then the runtime uses the weights each pallet ships with.
+#
+# `pallet_grandpa` is benchmarked into `bench_output.txt` only: its benchmarks do not measure
+# `report_equivocation`, so the runtime keeps the pallet's `()` weights.
+
+set -eu
+
+cd "$(dirname "$0")/.."
+
+PALLETS=(
+  frame_system
+  pallet_balances
+  pallet_timestamp
+  pallet_contracts
+  pallet_scheduler
+  pallet_preimage
+  pallet_node_authorization
+  pallet_assets
+  pallet_collective_council
+  pallet_collective_technical_committee
+  pallet_membership_council
+  pallet_membership_technical_committee
+  pallet_democracy
+  pallet_treasury
+  pallet_bounties
+  pallet_utility
+  pallet_multisig
+  pallet_proxy
+  pallet_identity
+  pallet_vesting
+  pallet_solar_nft
+  pallet_solar_market
+  pallet_solar_call_filter
+  pallet_solar_validator_set
+  pallet_solar_randomness
+  pallet_solar_block_reward
+)
+
+if [[ $# -gt 0 ]]; then
+  PALLETS=("$@")
+fi
+
+cargo build --release --features runtime-benchmarks
+mkdir -p runtime/src/weights
+
+for pallet in "${PALLETS[@]}"; do
+  echo "Benchmarking $pallet"
+  ./target/release/solar-node benchmark pallet \
+    --chain=dev \
+    --steps=50 \
+    --repeat=20 \
+    --pallet="$pallet" \
+    --extrinsic='*' \
+    --execution=wasm \
+    --wasm-execution=compiled \
+    --heap-pages=4096 \
+    --output="runtime/src/weights/$pallet.rs"
+done
+
+echo "Benchmarking pallet_grandpa"
+./target/release/solar-node benchmark pallet \
+  --chain=dev \
+  --steps=50 \
+  --repeat=20 \
+  --pallet=pallet_grandpa \
+  --extrinsic='*' \
+  --execution=wasm \
+  --wasm-execution=compiled \
+  --heap-pages=4096 | tee bench_output.txt